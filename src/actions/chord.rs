@@ -0,0 +1,586 @@
+use super::{keymap_action, Action, Context, Event, Keymap, Overrider};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The default time to wait for the next key of a sequence.
+const DEFAULT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Key bindings that map sequences of [`Event`]s, such as `CTRL` + `X` followed by `CTRL` +
+/// `E`, to an [`Action`].
+///
+/// While the keys typed so far are the prefix of a bound sequence, they are buffered and
+/// [`Noop`] is returned. If the next key does not continue the sequence, or if none arrives
+/// within the [`timeout`], the buffered keys are replayed with the behavior of the [`keymap`],
+/// and the next key is then matched on its own. Keys that do not start any sequence fall back to
+/// the default behavior.
+///
+/// A sequence cannot be both bound to an [`Action`] and be the prefix of a longer sequence.
+/// Inserting one will replace the other.
+///
+/// # Example
+///
+/// ```
+/// use rucline::Prompt;
+/// use rucline::actions::{Action, ChordBindings, Event, Scope};
+/// use crossterm::event::{KeyCode, KeyModifiers};
+///
+/// let mut bindings = ChordBindings::new();
+/// bindings.insert(
+///     &[
+///         Event::new(KeyCode::Char('x'), KeyModifiers::CONTROL),
+///         Event::new(KeyCode::Char('u'), KeyModifiers::CONTROL),
+///     ],
+///     Action::Delete(Scope::WholeLine),
+/// );
+///
+/// let prompt = Prompt::new().overrider(bindings);
+/// ```
+///
+/// [`Event`]: type.Event.html
+/// [`Action`]: enum.Action.html
+/// [`Noop`]: enum.Action.html#variant.Noop
+/// [`timeout`]: struct.ChordBindings.html#method.timeout
+/// [`keymap`]: struct.ChordBindings.html#method.keymap
+pub struct ChordBindings {
+    root: Node,
+    timeout: Option<std::time::Duration>,
+    keymap: Keymap,
+    pending: std::cell::RefCell<Option<Pending>>,
+}
+
+#[derive(Default)]
+struct Node {
+    action: Option<Action>,
+    children: std::collections::HashMap<Event, Node>,
+}
+
+/// The keys of an incomplete sequence and when the last one arrived.
+struct Pending {
+    keys: Vec<Event>,
+    since: std::time::Instant,
+}
+
+impl ChordBindings {
+    /// Creates an empty set of bindings, waiting up to one second for the next key of a
+    /// sequence.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how long to wait for the next key of a sequence. If `None`, the bindings will wait
+    /// indefinitely.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The maximum time between two keys of a sequence
+    #[must_use]
+    pub fn timeout(mut self, timeout: Option<std::time::Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the [`Keymap`] that replays the keys of a sequence that was not completed. It should
+    /// be the keymap of the prompt. The default is [`Keymap::Rucline`].
+    ///
+    /// # Arguments
+    ///
+    /// * `keymap` - The keymap for the keys of incomplete sequences
+    ///
+    /// [`Keymap`]: enum.Keymap.html
+    /// [`Keymap::Rucline`]: enum.Keymap.html#variant.Rucline
+    #[must_use]
+    pub fn keymap(mut self, keymap: Keymap) -> Self {
+        self.keymap = keymap;
+        self
+    }
+
+    /// Binds the sequence of `keys` to `action`, returning the [`Action`] previously bound to
+    /// that sequence, if any.
+    ///
+    /// Any binding for a prefix of `keys`, or for a sequence starting with `keys`, is removed.
+    /// An empty sequence is ignored.
+    ///
+    /// [`Action`]: enum.Action.html
+    pub fn insert(&mut self, keys: &[Event], action: Action) -> Option<Action> {
+        if keys.is_empty() {
+            return None;
+        }
+
+        let mut node = &mut self.root;
+        for key in keys {
+            node.action = None;
+            node = node.children.entry(*key).or_default();
+        }
+
+        node.children.clear();
+        node.action.replace(action)
+    }
+
+    /// Removes the binding for the sequence of `keys`, returning its [`Action`], if any.
+    ///
+    /// [`Action`]: enum.Action.html
+    pub fn remove(&mut self, keys: &[Event]) -> Option<Action> {
+        self.root.remove(keys)
+    }
+
+    /// Returns the [`Action`] bound to the sequence of `keys`, if any.
+    ///
+    /// [`Action`]: enum.Action.html
+    #[must_use]
+    pub fn get(&self, keys: &[Event]) -> Option<Action> {
+        self.root.find(keys).and_then(|node| node.action.clone())
+    }
+
+    /// Returns all the bound sequences along with their [`Action`]s.
+    ///
+    /// [`Action`]: enum.Action.html
+    #[must_use]
+    pub fn bindings(&self) -> Vec<(Vec<Event>, Action)> {
+        let mut bindings = Vec::new();
+        self.root.collect(&mut Vec::new(), &mut bindings);
+        bindings
+    }
+
+    fn is_stale(&self, pending: &Pending) -> bool {
+        self.timeout
+            .is_some_and(|timeout| pending.since.elapsed() > timeout)
+    }
+
+    /// The actions of the [`keymap`] for `keys` that did not complete a sequence.
+    ///
+    /// [`keymap`]: struct.ChordBindings.html#method.keymap
+    fn replay(&self, keys: &[Event], context: &dyn Context) -> Vec<Action> {
+        keys.iter()
+            .map(|key| keymap_action(self.keymap, *key, context))
+            .collect()
+    }
+}
+
+impl Node {
+    fn find(&self, keys: &[Event]) -> Option<&Self> {
+        keys.iter()
+            .try_fold(self, |node, key| node.children.get(key))
+    }
+
+    /// Takes the action bound to `keys` below this node, pruning every node left empty.
+    fn remove(&mut self, keys: &[Event]) -> Option<Action> {
+        let (first, rest) = keys.split_first()?;
+        let child = self.children.get_mut(first)?;
+
+        let removed = if rest.is_empty() {
+            child.action.take()
+        } else {
+            child.remove(rest)
+        };
+        if child.is_empty() {
+            self.children.remove(first);
+        }
+        removed
+    }
+
+    fn is_empty(&self) -> bool {
+        self.action.is_none() && self.children.is_empty()
+    }
+
+    fn collect(&self, prefix: &mut Vec<Event>, bindings: &mut Vec<(Vec<Event>, Action)>) {
+        if let Some(action) = &self.action {
+            bindings.push((prefix.clone(), action.clone()));
+        }
+
+        for (key, child) in &self.children {
+            prefix.push(*key);
+            child.collect(prefix, bindings);
+            prefix.pop();
+        }
+    }
+}
+
+impl Default for ChordBindings {
+    fn default() -> Self {
+        Self {
+            root: Node::default(),
+            timeout: Some(DEFAULT_TIMEOUT),
+            keymap: Keymap::default(),
+            pending: std::cell::RefCell::new(None),
+        }
+    }
+}
+
+impl Overrider for ChordBindings {
+    fn override_for(&self, event: Event, context: &dyn Context) -> Option<Action> {
+        let mut pending = self.pending.borrow_mut();
+        let mut dropped = Vec::new();
+
+        // Try to continue the pending sequence, if it is still fresh
+        if let Some(mut previous) = pending.take() {
+            if !self.is_stale(&previous) {
+                previous.keys.push(event);
+
+                // A node without an action or children binds nothing, so the keys are replayed
+                if let Some(node) = self
+                    .root
+                    .find(&previous.keys)
+                    .filter(|node| !node.is_empty())
+                {
+                    if node.children.is_empty() {
+                        return node.action.clone();
+                    }
+
+                    previous.since = std::time::Instant::now();
+                    *pending = Some(previous);
+                    return Some(Action::Noop);
+                }
+                previous.keys.pop();
+            }
+            dropped = previous.keys;
+        }
+
+        // Otherwise, match the event on its own
+        let action = match self
+            .root
+            .children
+            .get(&event)
+            .filter(|node| !node.is_empty())
+        {
+            Some(node) if node.children.is_empty() => node.action.clone(),
+            Some(_) => {
+                *pending = Some(Pending {
+                    keys: vec![event],
+                    since: std::time::Instant::now(),
+                });
+                Some(Action::Noop)
+            }
+            None => None,
+        };
+
+        // The keys of the abandoned sequence go first, so none of them is lost
+        if dropped.is_empty() {
+            action
+        } else {
+            let mut actions = self.replay(&dropped, context);
+            actions.push(action.unwrap_or_else(|| keymap_action(self.keymap, event, context)));
+            Some(Action::Sequence(actions))
+        }
+    }
+
+    fn pending_timeout(&self) -> Option<std::time::Duration> {
+        let pending = self.pending.borrow();
+        let since = pending.as_ref()?.since;
+        self.timeout
+            .map(|timeout| timeout.saturating_sub(since.elapsed()))
+    }
+
+    fn override_timeout(&self, context: &dyn Context) -> Option<Action> {
+        let pending = self.pending.borrow_mut().take()?;
+        Some(Action::Sequence(self.replay(&pending.keys, context)))
+    }
+}
+
+/// The serialized form of [`ChordBindings`](struct.ChordBindings.html).
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct Serialized {
+    timeout: Option<std::time::Duration>,
+    #[serde(default)]
+    keymap: Keymap,
+    bindings: Vec<(Vec<Event>, Action)>,
+}
+
+#[cfg(feature = "serde")]
+impl From<&ChordBindings> for Serialized {
+    fn from(bindings: &ChordBindings) -> Self {
+        Self {
+            timeout: bindings.timeout,
+            keymap: bindings.keymap,
+            bindings: bindings.bindings(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Serialized> for ChordBindings {
+    fn from(serialized: Serialized) -> Self {
+        let mut bindings = Self::new()
+            .timeout(serialized.timeout)
+            .keymap(serialized.keymap);
+        for (keys, action) in serialized.bindings {
+            bindings.insert(&keys, action);
+        }
+        bindings
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for ChordBindings {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Serialized::from(self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ChordBindings {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Serialized::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{keymap_action, Action, ChordBindings, Event, Keymap, Overrider};
+    use crate::actions::{Direction, Range, Scope};
+    use crate::test::mock::Context as Mock;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn control(c: char) -> Event {
+        Event::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    fn build_uut() -> ChordBindings {
+        let mut bindings = ChordBindings::new();
+        bindings.insert(
+            &[control('x'), control('u')],
+            Action::Delete(Scope::WholeLine),
+        );
+        bindings.insert(&[control('x'), control('x')], Action::Accept);
+        bindings.insert(&[Event::from(KeyCode::Tab)], Action::Write('\t'));
+        bindings
+    }
+
+    #[test]
+    fn single_key_bindings() {
+        let bindings = build_uut();
+        let tab = Event::from(KeyCode::Tab);
+        assert_eq!(
+            bindings.override_for(tab, &Mock::empty()),
+            Some(Action::Write('\t'))
+        );
+    }
+
+    #[test]
+    fn buffer_prefixes() {
+        let bindings = build_uut();
+        let context = Mock::empty();
+
+        assert_eq!(
+            bindings.override_for(control('x'), &context),
+            Some(Action::Noop)
+        );
+        assert_eq!(
+            bindings.override_for(control('u'), &context),
+            Some(Action::Delete(Scope::WholeLine))
+        );
+
+        // The sequence is over
+        assert_eq!(bindings.override_for(control('u'), &context), None);
+    }
+
+    fn keymap(key: Event) -> Action {
+        keymap_action(Keymap::default(), key, &Mock::empty())
+    }
+
+    #[test]
+    fn fall_back_if_sequence_does_not_match() {
+        let bindings = build_uut();
+        let context = Mock::empty();
+
+        assert_eq!(
+            bindings.override_for(control('x'), &context),
+            Some(Action::Noop)
+        );
+        assert_eq!(
+            bindings.override_for(control('a'), &context),
+            Some(Action::Sequence(vec![
+                keymap(control('x')),
+                keymap(control('a'))
+            ]))
+        );
+
+        // A mismatch may start a new sequence
+        assert_eq!(
+            bindings.override_for(control('x'), &context),
+            Some(Action::Noop)
+        );
+        assert_eq!(
+            bindings.override_for(Event::from(KeyCode::Tab), &context),
+            Some(Action::Sequence(vec![
+                keymap(control('x')),
+                Action::Write('\t')
+            ]))
+        );
+        assert_eq!(
+            bindings.override_for(control('x'), &context),
+            Some(Action::Noop)
+        );
+        assert_eq!(
+            bindings.override_for(control('x'), &context),
+            Some(Action::Accept)
+        );
+    }
+
+    #[test]
+    fn drop_stale_prefixes() {
+        let bindings = build_uut().timeout(Some(std::time::Duration::from_millis(1)));
+        let context = Mock::empty();
+
+        assert_eq!(
+            bindings.override_for(control('x'), &context),
+            Some(Action::Noop)
+        );
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        assert_eq!(
+            bindings.override_for(control('u'), &context),
+            Some(Action::Sequence(vec![
+                keymap(control('x')),
+                keymap(control('u'))
+            ]))
+        );
+    }
+
+    #[test]
+    fn replay_prefix_before_unrelated_key() {
+        let mut bindings = ChordBindings::new();
+        let esc = Event::from(KeyCode::Esc);
+        bindings.insert(&[esc, esc], Action::Delete(Scope::WholeLine));
+        let context = Mock::empty();
+
+        assert_eq!(bindings.override_for(esc, &context), Some(Action::Noop));
+        assert_eq!(
+            bindings.override_for(Event::from(KeyCode::Char('a')), &context),
+            Some(Action::Sequence(vec![Action::Cancel, Action::Write('a')]))
+        );
+        assert_eq!(bindings.pending_timeout(), None);
+    }
+
+    #[test]
+    fn replay_prefix_on_timeout() {
+        let mut bindings = ChordBindings::new().timeout(Some(std::time::Duration::from_millis(1)));
+        let esc = Event::from(KeyCode::Esc);
+        bindings.insert(&[esc, esc], Action::Delete(Scope::WholeLine));
+        let context = Mock::empty();
+
+        assert_eq!(bindings.pending_timeout(), None);
+        assert_eq!(bindings.override_timeout(&context), None);
+
+        assert_eq!(bindings.override_for(esc, &context), Some(Action::Noop));
+        assert!(bindings.pending_timeout().is_some());
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        assert_eq!(
+            bindings.pending_timeout(),
+            Some(std::time::Duration::from_millis(0))
+        );
+
+        assert_eq!(
+            bindings.override_timeout(&context),
+            Some(Action::Sequence(vec![Action::Cancel]))
+        );
+        assert_eq!(bindings.pending_timeout(), None);
+
+        // Without a timeout, the prompt waits for the next key
+        let bindings = bindings.timeout(None);
+        assert_eq!(bindings.override_for(esc, &context), Some(Action::Noop));
+        assert_eq!(bindings.pending_timeout(), None);
+    }
+
+    #[test]
+    fn prefixes_cannot_be_bound() {
+        let mut bindings = build_uut();
+        let esc = Event::from(KeyCode::Esc);
+
+        bindings.insert(&[esc], Action::Cancel);
+        assert_eq!(bindings.get(&[esc]), Some(Action::Cancel));
+
+        bindings.insert(&[esc, esc], Action::Delete(Scope::WholeLine));
+        assert_eq!(bindings.get(&[esc]), None);
+        assert_eq!(
+            bindings.get(&[esc, esc]),
+            Some(Action::Delete(Scope::WholeLine))
+        );
+
+        bindings.insert(
+            &[control('x')],
+            Action::Move(Range::Word, Direction::Forward),
+        );
+        assert_eq!(bindings.get(&[control('x'), control('u')]), None);
+        assert_eq!(bindings.bindings().len(), 3);
+    }
+
+    #[test]
+    fn remove_bindings() {
+        let mut bindings = build_uut();
+
+        assert_eq!(
+            bindings.remove(&[control('x'), control('u')]),
+            Some(Action::Delete(Scope::WholeLine))
+        );
+        assert_eq!(bindings.remove(&[control('x'), control('u')]), None);
+        assert_eq!(bindings.remove(&[control('x')]), None);
+        assert_eq!(
+            bindings.get(&[control('x'), control('x')]),
+            Some(Action::Accept)
+        );
+    }
+
+    #[test]
+    fn prune_emptied_sequences() {
+        let mut bindings = ChordBindings::new();
+        let context = Mock::empty();
+        let (a, b, c) = (
+            Event::from(KeyCode::Char('a')),
+            Event::from(KeyCode::Char('b')),
+            Event::from(KeyCode::Char('c')),
+        );
+
+        bindings.insert(&[a, b, c], Action::Accept);
+        assert_eq!(bindings.remove(&[a, b, c]), Some(Action::Accept));
+        assert!(bindings.root.children.is_empty());
+
+        // Nothing is bound anymore, so the keys are left to the keymap
+        assert_eq!(bindings.override_for(a, &context), None);
+        assert_eq!(bindings.override_for(b, &context), None);
+        assert_eq!(bindings.pending_timeout(), None);
+    }
+
+    #[test]
+    fn replay_prefix_reaching_an_empty_node() {
+        let mut bindings = ChordBindings::new();
+        let context = Mock::empty();
+        let (a, b) = (
+            Event::from(KeyCode::Char('a')),
+            Event::from(KeyCode::Char('b')),
+        );
+
+        // An empty node left behind by hand
+        bindings.insert(&[a, b], Action::Accept);
+        bindings
+            .root
+            .children
+            .get_mut(&a)
+            .unwrap()
+            .children
+            .get_mut(&b)
+            .unwrap()
+            .action = None;
+
+        assert_eq!(bindings.override_for(a, &context), Some(Action::Noop));
+        assert_eq!(
+            bindings.override_for(b, &context),
+            Some(Action::Sequence(vec![keymap(a), keymap(b)]))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn keep_keymap_through_serialization() {
+        use super::Serialized;
+
+        let mut bindings = ChordBindings::new().keymap(Keymap::Vi);
+        bindings.insert(&[control('x'), control('u')], Action::Accept);
+
+        let restored = ChordBindings::from(Serialized::from(&bindings));
+        assert_eq!(restored.keymap, Keymap::Vi);
+        assert_eq!(restored.timeout, bindings.timeout);
+        assert_eq!(restored.bindings(), bindings.bindings());
+    }
+}
@@ -0,0 +1,834 @@
+//! Provides a human-readable notation for [`Event`]s and [`Action`]s, and a loader for key
+//! binding files written with it.
+//!
+//! # Keys
+//!
+//! A key is written as its name, optionally preceded by the modifiers `ctrl-`, `alt-` and
+//! `shift-`, e.g. `ctrl-w`, `alt-b`, `shift-tab` or `f5`. Printable characters are their own
+//! name, with the exception of `space`. Other names are `enter`, `esc`, `tab`, `backspace`,
+//! `delete`, `insert`, `left`, `right`, `up`, `down`, `home`, `end`, `pageup`, `pagedown` and
+//! `f1` through `f12`.
+//!
+//! # Actions
+//!
+//! Actions are written in kebab case, e.g. `accept`, `delete-word-backward`,
+//! `move-line-forward`, `complete-word` or `write:#`. A [`Sequence`] is written as a list of
+//! actions separated by commas.
+//!
+//! The character searches are written `find-char-forward`, `till-char-backward`,
+//! `delete-find-char-forward`, etc. Followed by a `write` in a sequence, they jump to that
+//! character instead of waiting for the next key, e.g. `find-char-forward, write:/`.
+//!
+//! The [`Write`] action takes a single character after the colon, one of the names `space`,
+//! `tab`, `comma`, `backslash`, `newline`, `return` or `escape`, or the code point of any other
+//! character as `u+` followed by its hexadecimal value, e.g. `write:u+7`.
+//!
+//! The [`Paste`] action takes the text after the colon, where `\n`, `\r`, `\t`, `\s` (space),
+//! `\,` and `\\` stand for themselves, e.g. `paste:git\scommit\s-m`. Any other character may
+//! be written as its hexadecimal code point between `\u{` and `}`, e.g. `\u{1b}`.
+//!
+//! # Key binding files
+//!
+//! Each line binds a key to the actions it should perform, separated by `=`. Empty lines and
+//! lines starting with `#` are ignored.
+//!
+//! ```
+//! use rucline::actions::config;
+//!
+//! let bindings = config::parse_key_bindings(
+//!     r#"
+//!     ## Readline-like word deletion
+//!     ctrl-w = delete-word-backward
+//!     alt-d = delete-word-forward
+//!
+//!     ## Comment out the line and submit it
+//!     alt-# = move-line-backward, write:#, accept
+//!     "#,
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(bindings.len(), 3);
+//! ```
+//!
+//! [`Event`]: ../type.Event.html
+//! [`Action`]: ../enum.Action.html
+//! [`Sequence`]: ../enum.Action.html#variant.Sequence
+//! [`Write`]: ../enum.Action.html#variant.Write
+//! [`Paste`]: ../enum.Action.html#variant.Paste
+
+use super::{Action, Case, CharSearch, Direction, Event, KeyBindings, Range, Scope};
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// An error while reading key bindings.
+#[derive(Debug)]
+pub enum Error {
+    /// The key binding file could not be read
+    Io(std::io::Error),
+    /// The `line` (starting at 1) could not be parsed
+    Parse {
+        /// The offending line
+        line: usize,
+        /// A description of the problem
+        message: String,
+    },
+}
+
+impl Error {
+    fn parse(message: String) -> Self {
+        Error::Parse { line: 1, message }
+    }
+
+    fn at_line(self, number: usize) -> Self {
+        match self {
+            Error::Parse { message, .. } => Error::Parse {
+                line: number,
+                message,
+            },
+            Error::Io(_) => self,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(error) => error.fmt(fmt),
+            Error::Parse { line, message } => write!(fmt, "line {line}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            Error::Parse { .. } => None,
+        }
+    }
+}
+
+impl std::convert::From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+/// Parses a key such as `ctrl-w` into an [`Event`](../type.Event.html).
+///
+/// # Errors
+/// * [`Error::Parse`] - If the key or one of its modifiers is unknown.
+///
+/// [`Error::Parse`]: enum.Error.html#variant.Parse
+pub fn parse_key(string: &str) -> Result<Event, Error> {
+    let mut modifiers = KeyModifiers::empty();
+    let mut name = string.trim();
+
+    // A trailing dash is the key itself, e.g. `alt--`
+    while let Some(index) = name.find('-').filter(|index| index + 1 < name.len()) {
+        modifiers |= match name[..index].to_lowercase().as_str() {
+            "ctrl" | "control" | "c" => KeyModifiers::CONTROL,
+            "alt" | "meta" | "m" => KeyModifiers::ALT,
+            "shift" | "s" => KeyModifiers::SHIFT,
+            _ => break,
+        };
+        name = &name[index + 1..];
+    }
+
+    let mut chars = name.chars();
+    let code = match (chars.next(), chars.next()) {
+        (None, _) => return Err(Error::parse(format!("missing key in `{string}`"))),
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match name.to_lowercase().as_str() {
+            "space" => KeyCode::Char(' '),
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "null" => KeyCode::Null,
+            lower => match lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                Some(number) if (1..=12).contains(&number) => KeyCode::F(number),
+                _ => return Err(Error::parse(format!("unknown key `{string}`"))),
+            },
+        },
+    };
+
+    Ok(normalize(code, modifiers))
+}
+
+/// Matches the way the terminal reports keys, e.g. `shift-tab` is reported as
+/// `BackTab` and `shift-a` as an upper case `A` with `SHIFT`.
+pub(super) fn normalize(code: KeyCode, mut modifiers: KeyModifiers) -> Event {
+    let code = match code {
+        KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+            modifiers.remove(KeyModifiers::SHIFT);
+            KeyCode::BackTab
+        }
+        KeyCode::Char(c) if c.is_uppercase() => {
+            modifiers.insert(KeyModifiers::SHIFT);
+            KeyCode::Char(c)
+        }
+        KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) && c.is_lowercase() => {
+            KeyCode::Char(c.to_uppercase().next().unwrap_or(c))
+        }
+        code => code,
+    };
+
+    Event::new(code, modifiers)
+}
+
+/// Prints an [`Event`](../type.Event.html) in the notation accepted by
+/// [`parse_key`](fn.parse_key.html).
+#[must_use]
+pub fn print_key(event: Event) -> String {
+    let mut string = String::new();
+    let mut modifiers = event.modifiers;
+
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        string.push_str("ctrl-");
+    }
+    if modifiers.contains(KeyModifiers::ALT) {
+        string.push_str("alt-");
+    }
+
+    // The case of a character already carries the shift
+    if let KeyCode::Char(c) = event.code {
+        if c.is_uppercase() {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+    }
+    if modifiers.contains(KeyModifiers::SHIFT) {
+        string.push_str("shift-");
+    }
+
+    match event.code {
+        KeyCode::Char(' ') => string.push_str("space"),
+        KeyCode::Char(c) => string.push(c),
+        KeyCode::F(number) => {
+            string.push('f');
+            string.push_str(&number.to_string());
+        }
+        KeyCode::Enter => string.push_str("enter"),
+        KeyCode::Esc => string.push_str("esc"),
+        KeyCode::Tab => string.push_str("tab"),
+        KeyCode::BackTab => string.push_str("shift-tab"),
+        KeyCode::Backspace => string.push_str("backspace"),
+        KeyCode::Delete => string.push_str("delete"),
+        KeyCode::Insert => string.push_str("insert"),
+        KeyCode::Left => string.push_str("left"),
+        KeyCode::Right => string.push_str("right"),
+        KeyCode::Up => string.push_str("up"),
+        KeyCode::Down => string.push_str("down"),
+        KeyCode::Home => string.push_str("home"),
+        KeyCode::End => string.push_str("end"),
+        KeyCode::PageUp => string.push_str("pageup"),
+        KeyCode::PageDown => string.push_str("pagedown"),
+        KeyCode::Null => string.push_str("null"),
+    }
+
+    string
+}
+
+/// The names of the characters that a [`Write`] cannot take as is.
+///
+/// [`Write`]: ../enum.Action.html#variant.Write
+const CHAR_NAMES: &[(&str, char)] = &[
+    ("space", ' '),
+    ("tab", '\t'),
+    ("comma", ','),
+    ("backslash", '\\'),
+    ("newline", '\n'),
+    ("return", '\r'),
+    ("escape", '\x1b'),
+];
+
+/// Whether `c` would be lost or misread if printed as is, e.g. trimmed or breaking the line.
+fn needs_escape(c: char) -> bool {
+    c.is_control() || c.is_whitespace()
+}
+
+/// Parses a hexadecimal code point, such as `1b`.
+fn parse_code_point(hex: &str) -> Option<char> {
+    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+}
+
+/// Splits a sequence of actions on the commas that are not escaped.
+fn split_sequence(string: &str) -> Vec<&str> {
+    let mut actions = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+
+    for (index, c) in string.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            ',' => {
+                actions.push(&string[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    actions.push(&string[start..]);
+    actions
+}
+
+/// Resolves the escape sequences of a [`Paste`](../enum.Action.html#variant.Paste) text.
+fn unescape(text: &str) -> Option<String> {
+    let mut string = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            string.push(match chars.next()? {
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                's' => ' ',
+                c @ (',' | '\\') => c,
+                'u' => {
+                    let rest = chars.as_str().strip_prefix('{')?;
+                    let (hex, rest) = rest.split_at(rest.find('}')?);
+                    chars = rest[1..].chars();
+                    parse_code_point(hex)?
+                }
+                _ => return None,
+            });
+        } else {
+            string.push(c);
+        }
+    }
+
+    Some(string)
+}
+
+/// Escapes a [`Paste`](../enum.Action.html#variant.Paste) text, so it can be parsed back.
+fn escape(text: &str) -> String {
+    let mut string = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '\n' => string.push_str("\\n"),
+            '\r' => string.push_str("\\r"),
+            '\t' => string.push_str("\\t"),
+            ' ' => string.push_str("\\s"),
+            ',' => string.push_str("\\,"),
+            '\\' => string.push_str("\\\\"),
+            c if needs_escape(c) => string.extend(c.escape_unicode()),
+            c => string.push(c),
+        }
+    }
+
+    string
+}
+
+/// Parses a single action, without sequences.
+fn parse_single_action(string: &str) -> Result<Action, Error> {
+    use Direction::{Backward, Forward};
+    use Range::{Line, Single, Word};
+
+    if let Some(character) = string.strip_prefix("write:") {
+        let named = CHAR_NAMES
+            .iter()
+            .find(|(name, _)| *name == character)
+            .map(|(_, c)| *c);
+        let code_point = character.strip_prefix("u+").and_then(parse_code_point);
+        let mut chars = character.chars();
+        return match (named.or(code_point), chars.next(), chars.next()) {
+            (Some(c), _, _) | (None, Some(c), None) => Ok(Action::Write(c)),
+            _ => Err(Error::parse(format!(
+                "`{string}` must write a single character"
+            ))),
+        };
+    }
+
+    if let Some(text) = string.strip_prefix("paste:") {
+        return unescape(text).map(Action::Paste).ok_or_else(|| {
+            Error::parse(format!("`{string}` contains an unknown escape sequence"))
+        });
+    }
+
+    let action = match string {
+        "accept" => Action::Accept,
+        "cancel" => Action::Cancel,
+        "noop" => Action::Noop,
+        "suggest-forward" => Action::Suggest(Forward),
+        "suggest-backward" => Action::Suggest(Backward),
+        "complete-char" => Action::Complete(Single),
+        "complete-word" => Action::Complete(Word),
+        "complete-line" => Action::Complete(Line),
+        "delete-whole-word" => Action::Delete(Scope::WholeWord),
+        "delete-whole-line" => Action::Delete(Scope::WholeLine),
+        "yank" => Action::Yank,
+        "set-mark" => Action::SetMark,
+        "copy-region" => Action::CopyRegion,
+        "cut-region" => Action::CutRegion,
+        "copy-to-clipboard" => Action::CopyToClipboard,
+        "open-editor" => Action::OpenEditor,
+        "transpose-chars" => Action::TransposeChars,
+        "transpose-words" => Action::TransposeWords,
+        "upcase-word" => Action::ChangeCase(Case::Upper),
+        "downcase-word" => Action::ChangeCase(Case::Lower),
+        "capitalize-word" => Action::ChangeCase(Case::Capitalize),
+        "start-macro" => Action::StartMacro,
+        "end-macro" => Action::EndMacro,
+        "play-macro" => Action::PlayMacro,
+        "find-char-forward" => Action::MoveToChar(CharSearch::Find(Forward)),
+        "find-char-backward" => Action::MoveToChar(CharSearch::Find(Backward)),
+        "till-char-forward" => Action::MoveToChar(CharSearch::Till(Forward)),
+        "till-char-backward" => Action::MoveToChar(CharSearch::Till(Backward)),
+        "delete-find-char-forward" => Action::DeleteToChar(CharSearch::Find(Forward)),
+        "delete-find-char-backward" => Action::DeleteToChar(CharSearch::Find(Backward)),
+        "delete-till-char-forward" => Action::DeleteToChar(CharSearch::Till(Forward)),
+        "delete-till-char-backward" => Action::DeleteToChar(CharSearch::Till(Backward)),
+        _ => {
+            let mut parts = string.splitn(3, '-');
+            let verb = parts.next();
+            let range = match parts.next() {
+                Some("char") => Some(Single),
+                Some("word") => Some(Word),
+                Some("line") => Some(Line),
+                _ => None,
+            };
+            let direction = match parts.next() {
+                Some("forward") => Some(Forward),
+                Some("backward") => Some(Backward),
+                _ => None,
+            };
+
+            match (verb, range, direction) {
+                (Some("delete"), Some(range), Some(direction)) => {
+                    Action::Delete(Scope::Relative(range, direction))
+                }
+                (Some("move"), Some(range), Some(direction)) => Action::Move(range, direction),
+                (Some("select"), Some(range), Some(direction)) => Action::Select(range, direction),
+                _ => return Err(Error::parse(format!("unknown action `{string}`"))),
+            }
+        }
+    };
+
+    Ok(action)
+}
+
+impl std::str::FromStr for Action {
+    type Err = Error;
+
+    /// Parses an action in the notation described in the [`config`](config/index.html)
+    /// module, e.g. `delete-word-backward`.
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut actions = split_sequence(string)
+            .into_iter()
+            .map(str::trim)
+            .map(parse_single_action)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if actions.len() == 1 {
+            Ok(actions.remove(0))
+        } else {
+            Ok(Action::Sequence(actions))
+        }
+    }
+}
+
+impl std::fmt::Display for Action {
+    /// Prints the action in the notation described in the [`config`](config/index.html) module.
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Direction::{Backward, Forward};
+        use Range::{Line, Single, Word};
+
+        let range = |range| match range {
+            Single => "char",
+            Word => "word",
+            Line => "line",
+        };
+        let direction = |direction| match direction {
+            Forward => "forward",
+            Backward => "backward",
+        };
+        let char_search = |search| match search {
+            CharSearch::Find(d) => format!("find-char-{}", direction(d)),
+            CharSearch::Till(d) => format!("till-char-{}", direction(d)),
+        };
+
+        match self {
+            Action::Write(c) => match CHAR_NAMES.iter().find(|(_, named)| named == c) {
+                Some((name, _)) => write!(fmt, "write:{name}"),
+                None if needs_escape(*c) => write!(fmt, "write:u+{:x}", u32::from(*c)),
+                None => write!(fmt, "write:{c}"),
+            },
+            Action::Paste(text) => write!(fmt, "paste:{}", escape(text)),
+            Action::Delete(Scope::WholeWord) => fmt.write_str("delete-whole-word"),
+            Action::Delete(Scope::WholeLine) => fmt.write_str("delete-whole-line"),
+            Action::Delete(Scope::Relative(r, d)) => {
+                write!(fmt, "delete-{}-{}", range(*r), direction(*d))
+            }
+            Action::Move(r, d) => write!(fmt, "move-{}-{}", range(*r), direction(*d)),
+            Action::Suggest(d) => write!(fmt, "suggest-{}", direction(*d)),
+            Action::Complete(r) => write!(fmt, "complete-{}", range(*r)),
+            Action::Yank => fmt.write_str("yank"),
+            Action::SetMark => fmt.write_str("set-mark"),
+            Action::Select(r, d) => write!(fmt, "select-{}-{}", range(*r), direction(*d)),
+            Action::CopyRegion => fmt.write_str("copy-region"),
+            Action::CutRegion => fmt.write_str("cut-region"),
+            Action::CopyToClipboard => fmt.write_str("copy-to-clipboard"),
+            Action::OpenEditor => fmt.write_str("open-editor"),
+            Action::TransposeChars => fmt.write_str("transpose-chars"),
+            Action::TransposeWords => fmt.write_str("transpose-words"),
+            Action::ChangeCase(Case::Upper) => fmt.write_str("upcase-word"),
+            Action::ChangeCase(Case::Lower) => fmt.write_str("downcase-word"),
+            Action::ChangeCase(Case::Capitalize) => fmt.write_str("capitalize-word"),
+            Action::MoveToChar(search) => fmt.write_str(&char_search(*search)),
+            Action::DeleteToChar(search) => write!(fmt, "delete-{}", char_search(*search)),
+            Action::Accept => fmt.write_str("accept"),
+            Action::Cancel => fmt.write_str("cancel"),
+            Action::Sequence(actions) => {
+                for (index, action) in actions.iter().enumerate() {
+                    if index > 0 {
+                        fmt.write_str(", ")?;
+                    }
+                    action.fmt(fmt)?;
+                }
+                Ok(())
+            }
+            Action::StartMacro => fmt.write_str("start-macro"),
+            Action::EndMacro => fmt.write_str("end-macro"),
+            Action::PlayMacro => fmt.write_str("play-macro"),
+            Action::Noop => fmt.write_str("noop"),
+        }
+    }
+}
+
+/// Parses the contents of a key binding file into [`KeyBindings`](../type.KeyBindings.html).
+///
+/// # Errors
+/// * [`Error::Parse`] - With the number of the first line that could not be parsed.
+///
+/// [`Error::Parse`]: enum.Error.html#variant.Parse
+pub fn parse_key_bindings(source: &str) -> Result<KeyBindings, Error> {
+    let mut bindings = KeyBindings::new();
+
+    for (index, line) in source.lines().enumerate() {
+        parse_line(line, &mut bindings).map_err(|error| error.at_line(index + 1))?;
+    }
+
+    Ok(bindings)
+}
+
+fn parse_line(line: &str, bindings: &mut KeyBindings) -> Result<(), Error> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(());
+    }
+
+    // The key itself may be `=`, as in `= = write:=` or `alt-= = noop`
+    let separator = line
+        .char_indices()
+        .skip(1)
+        .find(|&(index, c)| c == '=' && !line[..index].ends_with('-'))
+        .map(|(index, _)| index)
+        .ok_or_else(|| Error::parse(format!("expected `=` after `{line}`")))?;
+
+    let key = parse_key(&line[..separator])?;
+    bindings.insert(key, line[separator + 1..].parse()?);
+    Ok(())
+}
+
+/// Reads a key binding file into [`KeyBindings`](../type.KeyBindings.html).
+///
+/// # Errors
+/// * [`Error::Io`] - If the file could not be read.
+/// * [`Error::Parse`] - With the number of the first line that could not be parsed.
+///
+/// [`Error::Io`]: enum.Error.html#variant.Io
+/// [`Error::Parse`]: enum.Error.html#variant.Parse
+pub fn load_key_bindings(path: impl AsRef<std::path::Path>) -> Result<KeyBindings, Error> {
+    parse_key_bindings(&std::fs::read_to_string(path)?)
+}
+
+/// Prints [`KeyBindings`](../type.KeyBindings.html) in the format accepted by
+/// [`parse_key_bindings`](fn.parse_key_bindings.html), sorted by key.
+#[must_use]
+pub fn print_key_bindings(bindings: &KeyBindings) -> String {
+    let mut lines = bindings
+        .iter()
+        .map(|(event, action)| format!("{} = {}\n", print_key(*event), action))
+        .collect::<Vec<_>>();
+    lines.sort();
+    lines.concat()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_key, parse_key_bindings, print_key, print_key_bindings, Error};
+    use crate::actions::{Action, Direction, Event, Range, Scope};
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    #[test]
+    fn parse_keys() {
+        let control = KeyModifiers::CONTROL;
+        let alt = KeyModifiers::ALT;
+        let shift = KeyModifiers::SHIFT;
+
+        assert_eq!(
+            parse_key("ctrl-w").unwrap(),
+            Event::new(KeyCode::Char('w'), control)
+        );
+        assert_eq!(
+            parse_key("alt-b").unwrap(),
+            Event::new(KeyCode::Char('b'), alt)
+        );
+        assert_eq!(
+            parse_key("shift-tab").unwrap(),
+            Event::from(KeyCode::BackTab)
+        );
+        assert_eq!(
+            parse_key("shift-a").unwrap(),
+            Event::new(KeyCode::Char('A'), shift)
+        );
+        assert_eq!(
+            parse_key("A").unwrap(),
+            Event::new(KeyCode::Char('A'), shift)
+        );
+        assert_eq!(
+            parse_key("alt--").unwrap(),
+            Event::new(KeyCode::Char('-'), alt)
+        );
+        assert_eq!(parse_key("-").unwrap(), Event::from(KeyCode::Char('-')));
+        assert_eq!(parse_key("space").unwrap(), Event::from(KeyCode::Char(' ')));
+        assert_eq!(parse_key("F5").unwrap(), Event::from(KeyCode::F(5)));
+        assert_eq!(
+            parse_key("ctrl-alt-Left").unwrap(),
+            Event::new(KeyCode::Left, control | alt)
+        );
+
+        assert!(parse_key("ctrl-").is_err());
+        assert!(parse_key("hyper-x").is_err());
+        assert!(parse_key("f13").is_err());
+        assert!(parse_key("").is_err());
+    }
+
+    #[test]
+    fn print_keys() {
+        for key in &[
+            "ctrl-w",
+            "alt-b",
+            "shift-tab",
+            "A",
+            "alt--",
+            "space",
+            "f5",
+            "ctrl-alt-left",
+            "shift-up",
+        ] {
+            assert_eq!(&print_key(parse_key(key).unwrap()), key);
+        }
+    }
+
+    #[test]
+    fn parse_actions() {
+        assert_eq!(
+            "delete-word-backward".parse::<Action>().unwrap(),
+            Action::Delete(Scope::Relative(Range::Word, Direction::Backward))
+        );
+        assert_eq!(
+            "move-char-forward".parse::<Action>().unwrap(),
+            Action::Move(Range::Single, Direction::Forward)
+        );
+        assert_eq!("write:#".parse::<Action>().unwrap(), Action::Write('#'));
+        assert_eq!("write:comma".parse::<Action>().unwrap(), Action::Write(','));
+        assert_eq!(
+            "move-line-backward, write:#, accept"
+                .parse::<Action>()
+                .unwrap(),
+            Action::Sequence(vec![
+                Action::Move(Range::Line, Direction::Backward),
+                Action::Write('#'),
+                Action::Accept,
+            ])
+        );
+
+        assert_eq!(
+            r"paste:a b\,c\n, accept".parse::<Action>().unwrap(),
+            Action::Sequence(vec![Action::Paste(String::from("a b,c\n")), Action::Accept])
+        );
+
+        assert_eq!(
+            "write:backslash, accept".parse::<Action>().unwrap(),
+            Action::Sequence(vec![Action::Write('\\'), Action::Accept])
+        );
+        assert_eq!(
+            "write:u+7".parse::<Action>().unwrap(),
+            Action::Write('\x07')
+        );
+        assert_eq!(
+            r"paste:a\u{1b}b".parse::<Action>().unwrap(),
+            Action::Paste(String::from("a\x1bb"))
+        );
+
+        assert!("delete-word".parse::<Action>().is_err());
+        assert!("write:u+d800".parse::<Action>().is_err());
+        assert!(r"paste:\u{1b".parse::<Action>().is_err());
+        assert!(r"paste:\q".parse::<Action>().is_err());
+        assert!("write:ab".parse::<Action>().is_err());
+        assert!("jump".parse::<Action>().is_err());
+    }
+
+    #[test]
+    fn print_actions() {
+        for action in &[
+            "write:a",
+            "write:space",
+            "write:comma",
+            "delete-whole-word",
+            "delete-line-forward",
+            "move-word-backward",
+            "suggest-forward",
+            "complete-word",
+            "start-macro",
+            "yank",
+            "set-mark",
+            "select-word-forward",
+            "copy-region",
+            "cut-region",
+            "copy-to-clipboard",
+            "open-editor",
+            "transpose-chars",
+            "transpose-words",
+            "capitalize-word",
+            "till-char-backward",
+            "delete-find-char-forward",
+            "find-char-forward, write:comma",
+            r"paste:git\scommit\s-m\s\,\\\n",
+            "move-line-backward, write:#, accept",
+        ] {
+            assert_eq!(&action.parse::<Action>().unwrap().to_string(), action);
+        }
+    }
+
+    #[test]
+    fn round_trip_every_action() {
+        use crate::actions::{Case, CharSearch};
+
+        let ranges = [Range::Single, Range::Word, Range::Line];
+        let directions = [Direction::Forward, Direction::Backward];
+        let searches = directions
+            .iter()
+            .flat_map(|&d| vec![CharSearch::Find(d), CharSearch::Till(d)])
+            .collect::<Vec<_>>();
+
+        let mut actions = vec![
+            Action::Paste(String::from(" a, b\\c\n\r\t\x1b\u{a0}\u{2028}d ")),
+            Action::Delete(Scope::WholeLine),
+            Action::Delete(Scope::WholeWord),
+            Action::Yank,
+            Action::TransposeChars,
+            Action::TransposeWords,
+            Action::ChangeCase(Case::Upper),
+            Action::ChangeCase(Case::Lower),
+            Action::ChangeCase(Case::Capitalize),
+            Action::SetMark,
+            Action::CopyRegion,
+            Action::CutRegion,
+            Action::CopyToClipboard,
+            Action::OpenEditor,
+            Action::Accept,
+            Action::Cancel,
+            Action::StartMacro,
+            Action::EndMacro,
+            Action::PlayMacro,
+            Action::Noop,
+        ];
+        actions.extend(
+            [
+                'a', ' ', '\t', ',', '\\', '\n', '\r', '\x1b', '\x07', '#', '=', 'u', '\u{a0}',
+            ]
+            .iter()
+            .map(|&c| Action::Write(c)),
+        );
+        for &range in &ranges {
+            actions.push(Action::Complete(range));
+            for &direction in &directions {
+                actions.push(Action::Delete(Scope::Relative(range, direction)));
+                actions.push(Action::Move(range, direction));
+                actions.push(Action::Select(range, direction));
+            }
+        }
+        for &direction in &directions {
+            actions.push(Action::Suggest(direction));
+        }
+        for &search in &searches {
+            actions.push(Action::MoveToChar(search));
+            actions.push(Action::DeleteToChar(search));
+        }
+
+        for action in &actions {
+            assert_eq!(&action.to_string().parse::<Action>().unwrap(), action);
+        }
+
+        // Every action may be followed by another in a sequence, and bound to a key in a file
+        let mut bindings = crate::actions::KeyBindings::new();
+        for (action, key) in actions.iter().zip('\u{4e00}'..) {
+            let sequence = Action::Sequence(vec![action.clone(), Action::Accept]);
+            assert_eq!(sequence.to_string().parse::<Action>().unwrap(), sequence);
+            bindings.insert(Event::from(KeyCode::Char(key)), sequence);
+        }
+        assert_eq!(
+            parse_key_bindings(&print_key_bindings(&bindings)).unwrap(),
+            bindings
+        );
+    }
+
+    #[test]
+    fn parse_bindings() {
+        let bindings = parse_key_bindings(
+            "# Comment\n\
+             \n\
+             ctrl-w = delete-word-backward\n\
+             = = write:=\n\
+             tab=suggest-backward\n",
+        );
+
+        let bindings = bindings.unwrap();
+        assert_eq!(bindings.len(), 3);
+        assert_eq!(
+            bindings.get(&Event::from(KeyCode::Char('='))),
+            Some(&Action::Write('='))
+        );
+
+        let printed = print_key_bindings(&bindings);
+        assert_eq!(
+            printed,
+            "= = write:=\nctrl-w = delete-word-backward\ntab = suggest-backward\n"
+        );
+        assert_eq!(parse_key_bindings(&printed).unwrap(), bindings);
+    }
+
+    #[test]
+    fn point_at_offending_line() {
+        let error = parse_key_bindings("ctrl-w = delete-word-backward\n\nctrl-x = explode\n");
+        match error {
+            Err(Error::Parse { line, message }) => {
+                assert_eq!(line, 3);
+                assert_eq!(message, "unknown action `explode`");
+            }
+            _ => panic!("expected a parse error"),
+        }
+
+        let error = parse_key_bindings("ctrl-w delete-word-backward").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1: expected `=` after `ctrl-w delete-word-backward`"
+        );
+    }
+}
@@ -0,0 +1,795 @@
+//! Translates GNU readline `inputrc` files into rucline [`KeyBindings`] and [`Keymap`]s.
+//!
+//! The common subset of the format is understood:
+//!
+//! * Bindings of key sequences, such as `"\C-w": backward-kill-word`, or of key names, such as
+//!   `Meta-Rubout: backward-kill-word`, to readline functions
+//! * Bindings of key sequences to macros, such as `"\C-xd": "date\n"`
+//! * The `editing-mode` variable
+//! * The `$if`, `$else` and `$endif` conditionals on the `mode` and the application name
+//!
+//! Anything else, such as other variables, unknown functions or `$include`, is skipped and
+//! reported through [`unsupported`] instead of failing the whole file.
+//!
+//! # Example
+//!
+//! ```
+//! use rucline::Prompt;
+//! use rucline::actions::inputrc::Inputrc;
+//!
+//! let inputrc = Inputrc::parse(
+//!     r#"
+//!     set editing-mode vi
+//!     $if my-app
+//!         "\C-w": backward-kill-word
+//!         set bell-style none
+//!     $endif
+//!     "#,
+//!     "my-app",
+//! );
+//!
+//! assert_eq!(inputrc.unsupported().len(), 1);
+//!
+//! let prompt = Prompt::new()
+//!     .keymap(inputrc.keymap().unwrap_or_default())
+//!     .overrider(inputrc.chord_bindings());
+//! ```
+//!
+//! [`KeyBindings`]: ../type.KeyBindings.html
+//! [`Keymap`]: ../enum.Keymap.html
+//! [`unsupported`]: struct.Inputrc.html#method.unsupported
+
+use super::config::normalize;
+use super::{
+    Action, Case, CharSearch, ChordBindings, Direction, Event, KeyBindings, Keymap, Range, Scope,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// The bindings and settings read from an `inputrc` file.
+#[derive(Debug, Default)]
+pub struct Inputrc {
+    keymap: Option<Keymap>,
+    bindings: Vec<(Vec<Event>, Action)>,
+    unsupported: Vec<Unsupported>,
+}
+
+/// A line of an `inputrc` file that was skipped.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Unsupported {
+    /// The number of the skipped line, starting at 1
+    pub line: usize,
+    /// Why the line was skipped
+    pub reason: String,
+}
+
+impl std::fmt::Display for Unsupported {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(fmt, "line {}: {}", self.line, self.reason)
+    }
+}
+
+impl Inputrc {
+    /// Parses the contents of an `inputrc` file.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The contents of the file
+    /// * `application` - The name matched by `$if` conditionals, such as `$if bash`
+    #[must_use]
+    pub fn parse(source: &str, application: &str) -> Self {
+        let mut parser = Parser {
+            application,
+            conditions: Vec::new(),
+            vi_mode: false,
+            skipped_keymap: None,
+            inputrc: Self::default(),
+        };
+
+        for (index, line) in source.lines().enumerate() {
+            if let Err(reason) = parser.parse_line(line.trim()) {
+                parser.inputrc.unsupported.push(Unsupported {
+                    line: index + 1,
+                    reason,
+                });
+            }
+        }
+
+        parser.inputrc
+    }
+
+    /// Reads and parses an `inputrc` file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file to read
+    /// * `application` - The name matched by `$if` conditionals, such as `$if bash`
+    ///
+    /// # Errors
+    /// * `std::io::Error` - If the file could not be read.
+    pub fn load(
+        path: impl AsRef<std::path::Path>,
+        application: &str,
+    ) -> Result<Self, std::io::Error> {
+        Ok(Self::parse(&std::fs::read_to_string(path)?, application))
+    }
+
+    /// Reads and parses the user's `inputrc` file, looked up the same way readline does: the
+    /// `INPUTRC` environment variable, then `~/.inputrc`, then `/etc/inputrc`.
+    ///
+    /// # Errors
+    /// * `std::io::Error` - If none of the files could be read.
+    pub fn load_default(application: &str) -> Result<Self, std::io::Error> {
+        let candidates = std::env::var_os("INPUTRC")
+            .map(std::path::PathBuf::from)
+            .into_iter()
+            .chain(
+                std::env::var_os("HOME").map(|home| std::path::Path::new(&home).join(".inputrc")),
+            )
+            .chain(std::iter::once(std::path::PathBuf::from("/etc/inputrc")));
+
+        let mut error = std::io::Error::from(std::io::ErrorKind::NotFound);
+        for path in candidates {
+            match Self::load(&path, application) {
+                Ok(inputrc) => return Ok(inputrc),
+                Err(e) => error = e,
+            }
+        }
+        Err(error)
+    }
+
+    /// The [`Keymap`] selected with `set editing-mode`, if any.
+    ///
+    /// [`Keymap`]: ../enum.Keymap.html
+    #[must_use]
+    pub fn keymap(&self) -> Option<Keymap> {
+        self.keymap
+    }
+
+    /// The bindings of single keys.
+    ///
+    /// Bindings of sequences of several keys, such as `"\C-x\C-u"`, are only available through
+    /// [`chord_bindings`](#method.chord_bindings).
+    #[must_use]
+    pub fn key_bindings(&self) -> KeyBindings {
+        self.bindings
+            .iter()
+            .filter(|(keys, _)| keys.len() == 1)
+            .map(|(keys, action)| (keys[0], action.clone()))
+            .collect()
+    }
+
+    /// All the bindings, including sequences of several keys.
+    ///
+    /// The keys of an incomplete sequence are replayed with the selected [`keymap`], if any.
+    ///
+    /// [`keymap`]: #method.keymap
+    #[must_use]
+    pub fn chord_bindings(&self) -> ChordBindings {
+        let mut bindings = ChordBindings::new().keymap(self.keymap.unwrap_or_default());
+        for (keys, action) in &self.bindings {
+            bindings.insert(keys, action.clone());
+        }
+        bindings
+    }
+
+    /// The lines that were skipped, in order.
+    #[must_use]
+    pub fn unsupported(&self) -> &[Unsupported] {
+        &self.unsupported
+    }
+}
+
+struct Parser<'a> {
+    application: &'a str,
+    /// For each nested `$if`, whether its enclosing block is active and whether its own
+    /// condition holds
+    conditions: Vec<(bool, bool)>,
+    vi_mode: bool,
+    /// Bindings for keymaps that have no rucline equivalent are dropped
+    skipped_keymap: Option<String>,
+    inputrc: Inputrc,
+}
+
+impl Parser<'_> {
+    fn is_active(&self) -> bool {
+        self.conditions
+            .last()
+            .is_none_or(|(enclosing, condition)| *enclosing && *condition)
+    }
+
+    fn parse_line(&mut self, line: &str) -> Result<(), String> {
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(());
+        }
+
+        if let Some(directive) = line.strip_prefix('$') {
+            return self.parse_directive(directive);
+        }
+
+        if !self.is_active() {
+            return Ok(());
+        }
+
+        if let Some(setting) = line.strip_prefix("set ") {
+            self.parse_setting(setting.trim())
+        } else {
+            self.parse_binding(line)
+        }
+    }
+
+    fn parse_directive(&mut self, directive: &str) -> Result<(), String> {
+        let mut words = directive.splitn(2, char::is_whitespace);
+        let name = words.next().unwrap_or_default();
+        let argument = words.next().unwrap_or_default().trim();
+
+        match name {
+            "if" => {
+                let enclosing = self.is_active();
+                let condition = self.evaluate(argument);
+                self.conditions
+                    .push((enclosing, condition.as_ref().is_ok_and(|holds| *holds)));
+                condition.map(|_| ())
+            }
+            "else" => match self.conditions.last_mut() {
+                Some((_, condition)) => {
+                    *condition = !*condition;
+                    Ok(())
+                }
+                None => Err(String::from("`$else` without `$if`")),
+            },
+            "endif" => self
+                .conditions
+                .pop()
+                .map(|_| ())
+                .ok_or_else(|| String::from("`$endif` without `$if`")),
+            _ => Err(format!("unsupported directive `${name}`")),
+        }
+    }
+
+    fn evaluate(&self, condition: &str) -> Result<bool, String> {
+        if let Some(mode) = condition.strip_prefix("mode=") {
+            match mode.trim() {
+                "vi" => Ok(self.vi_mode),
+                "emacs" => Ok(!self.vi_mode),
+                mode => Err(format!("unknown mode `{mode}`")),
+            }
+        } else if condition.contains('=') || condition.contains(' ') {
+            Err(format!("unsupported condition `{condition}`"))
+        } else {
+            Ok(condition.eq_ignore_ascii_case(self.application))
+        }
+    }
+
+    fn parse_setting(&mut self, setting: &str) -> Result<(), String> {
+        let mut words = setting.split_whitespace();
+        let variable = words.next().unwrap_or_default();
+        let value = words.next().unwrap_or_default();
+
+        match (variable, value) {
+            ("editing-mode", "vi") => {
+                self.vi_mode = true;
+                self.inputrc.keymap = Some(Keymap::Vi);
+            }
+            ("editing-mode", "emacs") => {
+                self.vi_mode = false;
+                self.inputrc.keymap = Some(Keymap::Emacs);
+            }
+            ("keymap", "emacs" | "emacs-standard" | "vi" | "vi-insert") => {
+                self.skipped_keymap = None;
+            }
+            ("keymap", keymap) => {
+                self.skipped_keymap = Some(String::from(keymap));
+                return Err(format!("unsupported keymap `{keymap}`"));
+            }
+            _ => return Err(format!("unsupported variable `{variable}`")),
+        }
+
+        Ok(())
+    }
+
+    fn parse_binding(&mut self, line: &str) -> Result<(), String> {
+        let (keys, value) = if let Some(sequence) = line.strip_prefix('"') {
+            let end =
+                closing_quote(sequence).ok_or_else(|| String::from("unterminated key sequence"))?;
+            let value = sequence[end + 1..]
+                .trim_start()
+                .strip_prefix(':')
+                .ok_or_else(|| String::from("expected `:` after the key sequence"))?;
+            (to_events(&unescape(&sequence[..end])?)?, value)
+        } else {
+            let separator = line
+                .char_indices()
+                .skip(1)
+                .find(|&(_, c)| c == ':')
+                .map(|(index, _)| index)
+                .ok_or_else(|| String::from("expected `:` after the key name"))?;
+            (
+                vec![parse_key_name(line[..separator].trim())?],
+                &line[separator + 1..],
+            )
+        };
+
+        if let Some(keymap) = &self.skipped_keymap {
+            return Err(format!("bindings for keymap `{keymap}` are not supported"));
+        }
+
+        let value = value.trim();
+        let action = if let Some(text) = value.strip_prefix('"') {
+            let end = closing_quote(text).ok_or_else(|| String::from("unterminated macro"))?;
+            macro_action(&unescape(&text[..end])?)
+        } else {
+            let function = value.split_whitespace().next().unwrap_or_default();
+            function_action(function, &keys)?
+        };
+
+        self.inputrc.bindings.push((keys, action));
+        Ok(())
+    }
+}
+
+/// Finds the closing quote of a string whose opening quote was already stripped.
+fn closing_quote(string: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (index, c) in string.char_indices() {
+        match c {
+            '"' if !escaped => return Some(index),
+            '\\' => escaped = !escaped,
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+/// Resolves the escapes of a quoted key sequence or macro into the characters a terminal
+/// would send.
+fn unescape(string: &str) -> Result<Vec<char>, String> {
+    let mut chars = string.chars().peekable();
+    let mut output = Vec::new();
+    while chars.peek().is_some() {
+        output.extend(unescape_one(&mut chars)?);
+    }
+    Ok(output)
+}
+
+fn unescape_one(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> Result<Vec<char>, String> {
+    let c = chars
+        .next()
+        .ok_or_else(|| String::from("incomplete escape"))?;
+    if c != '\\' {
+        return Ok(vec![c]);
+    }
+
+    let escape = chars
+        .next()
+        .ok_or_else(|| String::from("incomplete escape"))?;
+    let c = match escape {
+        'C' | 'M' if chars.peek() == Some(&'-') => {
+            chars.next();
+            let mut next = unescape_one(chars)?;
+            if escape == 'M' {
+                next.insert(0, '\x1b');
+            } else if let Some(last) = next.last_mut() {
+                *last = control(*last);
+            }
+            return Ok(next);
+        }
+        'e' => '\x1b',
+        'a' => '\x07',
+        'b' => '\x08',
+        'd' => '\x7f',
+        'f' => '\x0c',
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        'v' => '\x0b',
+        'x' => {
+            let mut value = 0;
+            for _ in 0..2 {
+                match chars.peek().and_then(|c| c.to_digit(16)) {
+                    Some(digit) => value = value * 16 + digit,
+                    None => break,
+                }
+                chars.next();
+            }
+            std::char::from_u32(value).unwrap_or_default()
+        }
+        '0'..='7' => {
+            let mut value = escape.to_digit(8).unwrap_or_default();
+            for _ in 0..2 {
+                match chars.peek().and_then(|c| c.to_digit(8)) {
+                    Some(digit) => value = value * 8 + digit,
+                    None => break,
+                }
+                chars.next();
+            }
+            std::char::from_u32(value).unwrap_or_default()
+        }
+        c => c,
+    };
+
+    Ok(vec![c])
+}
+
+fn control(c: char) -> char {
+    if c == '?' {
+        '\x7f'
+    } else {
+        std::char::from_u32(u32::from(c) & 0x1f).unwrap_or(c)
+    }
+}
+
+/// Translates the characters a terminal would send into the [`Event`]s crossterm reports.
+fn to_events(chars: &[char]) -> Result<Vec<Event>, String> {
+    let mut events = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let (event, length) = if chars[index] == '\x1b' {
+            match chars.get(index + 1) {
+                None => (Event::from(KeyCode::Esc), 1),
+                Some('[' | 'O') => escape_sequence(&chars[index + 2..])
+                    .map(|(event, length)| (event, length + 2))
+                    .ok_or_else(|| String::from("unsupported escape sequence"))?,
+                Some(&c) => {
+                    let mut event = char_event(c);
+                    event.modifiers |= KeyModifiers::ALT;
+                    (event, 2)
+                }
+            }
+        } else {
+            (char_event(chars[index]), 1)
+        };
+
+        events.push(event);
+        index += length;
+    }
+
+    if events.is_empty() {
+        Err(String::from("empty key sequence"))
+    } else {
+        Ok(events)
+    }
+}
+
+fn char_event(c: char) -> Event {
+    match c {
+        '\t' => Event::from(KeyCode::Tab),
+        '\r' => Event::from(KeyCode::Enter),
+        '\x7f' => Event::from(KeyCode::Backspace),
+        '\x1b' => Event::from(KeyCode::Esc),
+        '\0' => Event::new(KeyCode::Char(' '), KeyModifiers::CONTROL),
+        '\x01'..='\x1a' => Event::new(
+            KeyCode::Char((b'a' + c as u8 - 1) as char),
+            KeyModifiers::CONTROL,
+        ),
+        '\x1c'..='\x1f' => Event::new(
+            KeyCode::Char((b'4' + c as u8 - 0x1c) as char),
+            KeyModifiers::CONTROL,
+        ),
+        c => normalize(KeyCode::Char(c), KeyModifiers::NONE),
+    }
+}
+
+/// Parses the part of a `CSI` or `SS3` sequence after `ESC [` or `ESC O`, returning the key
+/// and how many characters it took.
+fn escape_sequence(chars: &[char]) -> Option<(Event, usize)> {
+    let end = chars
+        .iter()
+        .position(|c| !c.is_ascii_digit() && *c != ';')?;
+    let parameters = chars[..end].iter().collect::<String>();
+    let mut parameters = parameters.split(';');
+    let number = parameters.next().unwrap_or_default();
+    let modifiers = match parameters.next().map(str::parse::<u8>) {
+        None => KeyModifiers::NONE,
+        Some(Ok(value)) => {
+            let value = value.saturating_sub(1);
+            let mut modifiers = KeyModifiers::NONE;
+            if value & 1 != 0 {
+                modifiers |= KeyModifiers::SHIFT;
+            }
+            if value & 2 != 0 {
+                modifiers |= KeyModifiers::ALT;
+            }
+            if value & 4 != 0 {
+                modifiers |= KeyModifiers::CONTROL;
+            }
+            modifiers
+        }
+        Some(Err(_)) => return None,
+    };
+
+    let code = match (chars[end], number) {
+        ('A', _) => KeyCode::Up,
+        ('B', _) => KeyCode::Down,
+        ('C', _) => KeyCode::Right,
+        ('D', _) => KeyCode::Left,
+        ('H', _) | ('~', "1" | "7") => KeyCode::Home,
+        ('F', _) | ('~', "4" | "8") => KeyCode::End,
+        ('Z', _) => KeyCode::BackTab,
+        ('~', "2") => KeyCode::Insert,
+        ('~', "3") => KeyCode::Delete,
+        ('~', "5") => KeyCode::PageUp,
+        ('~', "6") => KeyCode::PageDown,
+        _ => return None,
+    };
+
+    Some((Event::new(code, modifiers), end + 1))
+}
+
+/// Parses a key name such as `Control-u` or `Meta-Rubout`.
+fn parse_key_name(name: &str) -> Result<Event, String> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut key = name;
+
+    while let Some(index) = key.find('-').filter(|index| index + 1 < key.len()) {
+        modifiers |= match key[..index].to_lowercase().as_str() {
+            "control" | "c" => KeyModifiers::CONTROL,
+            "meta" | "m" => KeyModifiers::ALT,
+            _ => break,
+        };
+        key = &key[index + 1..];
+    }
+
+    let mut chars = key.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match key.to_lowercase().as_str() {
+            "rubout" | "del" => KeyCode::Backspace,
+            "esc" | "escape" => KeyCode::Esc,
+            "lfd" | "newline" => {
+                modifiers |= KeyModifiers::CONTROL;
+                KeyCode::Char('j')
+            }
+            "ret" | "return" => KeyCode::Enter,
+            "space" | "spc" => KeyCode::Char(' '),
+            "tab" => KeyCode::Tab,
+            _ => return Err(format!("unknown key name `{name}`")),
+        },
+    };
+
+    // Readline does not distinguish the case of control characters
+    let code = match code {
+        KeyCode::Char(c) if modifiers.contains(KeyModifiers::CONTROL) => {
+            KeyCode::Char(c.to_ascii_lowercase())
+        }
+        code => code,
+    };
+
+    Ok(normalize(code, modifiers))
+}
+
+/// Translates a macro into the actions that type it. Line breaks accept the line, as readline
+/// would.
+fn macro_action(chars: &[char]) -> Action {
+    Action::Sequence(
+        chars
+            .iter()
+            .map(|c| match c {
+                '\r' | '\n' => Action::Accept,
+                c => Action::Write(*c),
+            })
+            .collect(),
+    )
+}
+
+/// Translates a readline function into an [`Action`](../enum.Action.html).
+fn function_action(function: &str, keys: &[Event]) -> Result<Action, String> {
+    use Direction::{Backward, Forward};
+    use Range::{Line, Single, Word};
+
+    let action = match function.to_lowercase().as_str() {
+        "accept-line" => Action::Accept,
+        "beginning-of-line" => Action::Move(Line, Backward),
+        "end-of-line" => Action::Move(Line, Forward),
+        "forward-char" => Action::Move(Single, Forward),
+        "backward-char" => Action::Move(Single, Backward),
+        "forward-word" => Action::Move(Word, Forward),
+        "backward-word" => Action::Move(Word, Backward),
+        "delete-char" => Action::Delete(Scope::Relative(Single, Forward)),
+        "backward-delete-char" => Action::Delete(Scope::Relative(Single, Backward)),
+        "kill-line" => Action::Delete(Scope::Relative(Line, Forward)),
+        "backward-kill-line" | "unix-line-discard" => {
+            Action::Delete(Scope::Relative(Line, Backward))
+        }
+        "kill-whole-line" => Action::Delete(Scope::WholeLine),
+        "kill-word" => Action::Delete(Scope::Relative(Word, Forward)),
+        "backward-kill-word" | "unix-word-rubout" => {
+            Action::Delete(Scope::Relative(Word, Backward))
+        }
+        "yank" => Action::Yank,
+        "set-mark" => Action::SetMark,
+        "kill-region" => Action::CutRegion,
+        "copy-region-as-kill" => Action::CopyRegion,
+        // The edited line is not executed right away, it may still be reviewed
+        "edit-and-execute-command" => Action::OpenEditor,
+        "transpose-chars" => Action::TransposeChars,
+        "transpose-words" => Action::TransposeWords,
+        "character-search" => Action::MoveToChar(CharSearch::Find(Forward)),
+        "character-search-backward" => Action::MoveToChar(CharSearch::Find(Backward)),
+        "upcase-word" => Action::ChangeCase(Case::Upper),
+        "downcase-word" => Action::ChangeCase(Case::Lower),
+        "capitalize-word" => Action::ChangeCase(Case::Capitalize),
+        "complete" | "menu-complete" => Action::Suggest(Forward),
+        "menu-complete-backward" => Action::Suggest(Backward),
+        "start-kbd-macro" => Action::StartMacro,
+        "end-kbd-macro" => Action::EndMacro,
+        "call-last-kbd-macro" => Action::PlayMacro,
+        "self-insert" => match keys {
+            [Event {
+                code: KeyCode::Char(c),
+                ..
+            }] => Action::Write(*c),
+            _ => return Err(String::from("`self-insert` needs a single printable key")),
+        },
+        _ => return Err(format!("unsupported function `{function}`")),
+    };
+
+    Ok(action)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Inputrc, Unsupported};
+    use crate::actions::{Action, Direction, Event, Keymap, Range, Scope};
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn control(c: char) -> Event {
+        Event::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    fn alt(c: char) -> Event {
+        Event::new(KeyCode::Char(c), KeyModifiers::ALT)
+    }
+
+    #[test]
+    fn parse_key_sequences() {
+        let inputrc = Inputrc::parse(
+            r#"
+            "\C-w": backward-kill-word
+            "\ef": forward-word
+            "\M-b": backward-word
+            "\e[1;5C": forward-word
+            "\C-x\C-u": kill-whole-line
+            "\t": menu-complete
+            "#,
+            "test",
+        );
+
+        assert!(inputrc.unsupported().is_empty());
+
+        let bindings = inputrc.key_bindings();
+        assert_eq!(bindings.len(), 5);
+        assert_eq!(
+            bindings.get(&control('w')),
+            Some(&Action::Delete(Scope::Relative(
+                Range::Word,
+                Direction::Backward
+            )))
+        );
+        assert_eq!(
+            bindings.get(&alt('f')),
+            Some(&Action::Move(Range::Word, Direction::Forward))
+        );
+        assert_eq!(
+            bindings.get(&alt('b')),
+            Some(&Action::Move(Range::Word, Direction::Backward))
+        );
+        assert_eq!(
+            bindings.get(&Event::new(KeyCode::Right, KeyModifiers::CONTROL)),
+            Some(&Action::Move(Range::Word, Direction::Forward))
+        );
+        assert_eq!(
+            bindings.get(&Event::from(KeyCode::Tab)),
+            Some(&Action::Suggest(Direction::Forward))
+        );
+
+        assert_eq!(
+            inputrc.chord_bindings().get(&[control('x'), control('u')]),
+            Some(Action::Delete(Scope::WholeLine))
+        );
+    }
+
+    #[test]
+    fn parse_key_names_and_macros() {
+        let inputrc = Inputrc::parse(
+            "Control-u: unix-line-discard\n\
+             Meta-Rubout: backward-kill-word\n\
+             \"\\C-xd\": \"ls\\n\"\n",
+            "test",
+        );
+
+        assert!(inputrc.unsupported().is_empty());
+        assert_eq!(
+            inputrc.key_bindings().get(&control('u')),
+            Some(&Action::Delete(Scope::Relative(
+                Range::Line,
+                Direction::Backward
+            )))
+        );
+        assert_eq!(
+            inputrc
+                .key_bindings()
+                .get(&Event::new(KeyCode::Backspace, KeyModifiers::ALT)),
+            Some(&Action::Delete(Scope::Relative(
+                Range::Word,
+                Direction::Backward
+            )))
+        );
+        assert_eq!(
+            inputrc
+                .chord_bindings()
+                .get(&[control('x'), Event::from(KeyCode::Char('d'))]),
+            Some(Action::Sequence(vec![
+                Action::Write('l'),
+                Action::Write('s'),
+                Action::Accept
+            ]))
+        );
+    }
+
+    #[test]
+    fn evaluate_conditionals() {
+        let source = r#"
+            set editing-mode vi
+            $if mode=emacs
+                "\C-a": beginning-of-line
+            $else
+                "\C-b": beginning-of-line
+            $endif
+            $if Bash
+                "\C-c": accept-line
+            $else
+                $if mode=vi
+                    "\C-d": accept-line
+                $endif
+            $endif
+            "#;
+
+        let inputrc = Inputrc::parse(source, "bash");
+        assert_eq!(inputrc.keymap(), Some(Keymap::Vi));
+        assert!(inputrc.unsupported().is_empty());
+
+        let mut keys = inputrc
+            .key_bindings()
+            .keys()
+            .map(|event| event.code)
+            .collect::<Vec<_>>();
+        keys.sort_by_key(|code| format!("{code:?}"));
+        assert_eq!(keys, vec![KeyCode::Char('b'), KeyCode::Char('c')]);
+
+        let inputrc = Inputrc::parse(source, "python");
+        let mut keys = inputrc
+            .key_bindings()
+            .keys()
+            .map(|event| event.code)
+            .collect::<Vec<_>>();
+        keys.sort_by_key(|code| format!("{code:?}"));
+        assert_eq!(keys, vec![KeyCode::Char('b'), KeyCode::Char('d')]);
+    }
+
+    #[test]
+    fn report_unsupported_lines() {
+        let inputrc = Inputrc::parse(
+            "set bell-style none\n\
+             \"\\C-t\": transpose-words-and-dance\n\
+             $include /etc/inputrc\n\
+             \"\\C-a\": beginning-of-line\n\
+             set keymap vi-command\n\
+             \"x\": delete-char\n\
+             $endif\n",
+            "test",
+        );
+
+        let lines = inputrc
+            .unsupported()
+            .iter()
+            .map(|Unsupported { line, .. }| *line)
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec![1, 2, 3, 5, 6, 7]);
+        assert_eq!(
+            inputrc.unsupported()[1].to_string(),
+            "line 2: unsupported function `transpose-words-and-dance`"
+        );
+        assert_eq!(inputrc.key_bindings().len(), 1);
+    }
+}
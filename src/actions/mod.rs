@@ -0,0 +1,1171 @@
+//! Provides mappings and actions to change the behavior of [`Prompt`] when parsing the user input.
+//!
+//! There is a built-in set of default [`Action`]s that will be executed upon user interaction.
+//! These are meant to feel natural when coming from the default terminal, while also adding further
+//! functionality and editing commands.
+//!
+//! However, bindings that override the default behavior can be given to [`Prompt`] to cause
+//! a different [`Action`] to be taken.
+//!
+//! # Examples
+//!
+//! ```
+//! use rucline::Prompt;
+//! use rucline::actions::{Action, Event, KeyBindings};
+//! use crossterm::event::KeyCode;
+//!
+//! let mut bindings = KeyBindings::new();
+//! bindings.insert(Event::from(KeyCode::Tab), Action::Write('\t'));
+//!
+//! let prompt = Prompt::new().overrider(bindings);
+//! ```
+//!
+//! ```
+//! use rucline::Prompt;
+//! use rucline::actions::{Action, Context, Event, KeyBindings};
+//! use crossterm::event::KeyCode;
+//!
+//! let prompt = Prompt::new().overrider(|e, _: &dyn Context| if e == Event::from(KeyCode::Tab) {
+//!     Some(Action::Write('\t'))
+//! } else {
+//!     None
+//! });
+//! ```
+//!
+//! # Overriding a default action
+//!
+//! The [`KeyBindings`] map provides a way to store the association
+//! between [`Event`] and [`Action`] which will override the default behavior.
+//!
+//! ```
+//! use rucline::actions::{Action, Event, KeyBindings};
+//! use crossterm::event::KeyCode;
+//!
+//! let mut bindings = KeyBindings::new();
+//! bindings.insert(Event::from(KeyCode::Tab), Action::Write('\t'));
+//! ```
+//!
+//! # Binding sequences of keys
+//!
+//! The [`ChordBindings`] map associates sequences of [`Event`]s, such as `CTRL` + `X` followed
+//! by `CTRL` + `E`, with an [`Action`].
+//!
+//! ```
+//! use rucline::actions::{Action, ChordBindings, Event};
+//! use crossterm::event::KeyCode;
+//!
+//! let mut bindings = ChordBindings::new();
+//! bindings.insert(&[Event::from(KeyCode::Esc), Event::from(KeyCode::Esc)], Action::Cancel);
+//! ```
+//!
+//! # Binding a sequence of actions
+//!
+//! A single [`Event`] can perform several [`Action`]s in order by binding it to a
+//! [`Sequence`]. For instance, to comment out and submit the line:
+//!
+//! ```
+//! use rucline::actions::{Action, Direction, Event, KeyBindings, Range};
+//! use crossterm::event::{KeyCode, KeyModifiers};
+//!
+//! let mut bindings = KeyBindings::new();
+//! bindings.insert(
+//!     Event::new(KeyCode::Char('#'), KeyModifiers::ALT),
+//!     Action::Sequence(vec![
+//!         Action::Move(Range::Line, Direction::Backward),
+//!         Action::Write('#'),
+//!         Action::Accept,
+//!     ]),
+//! );
+//! ```
+//!
+//! Sequences can also be recorded at run-time as a keyboard macro with [`StartMacro`] and
+//! [`EndMacro`], and replayed with [`PlayMacro`].
+//!
+//! # Disabling a default action
+//!
+//! To explicitly remove an [`Action`] from the default behavior, the [`Noop`] action can be
+//! set as the override.
+//!
+//! ```
+//! use rucline::actions::{Action, Event, KeyBindings};
+//! use crossterm::event::KeyCode;
+//!
+//! let mut bindings = KeyBindings::new();
+//! bindings.insert(Event::from(KeyCode::Tab), Action::Noop);
+//! ```
+//!
+//! # Selecting a keymap
+//!
+//! The bindings that apply in the absence of an override are defined by a [`Keymap`], which
+//! can be selected on the [`Prompt`].
+//!
+//! ```
+//! use rucline::Prompt;
+//! use rucline::actions::Keymap;
+//!
+//! let prompt = Prompt::new().keymap(Keymap::Vi);
+//! ```
+//!
+//! The available presets are the [`Rucline`] default described [below](#default-behavior),
+//! an [`Emacs`] keymap faithful to GNU readline, a [`Minimal`] one that only covers the
+//! essentials, and the modal [`Vi`] keymap.
+//!
+//! # Pasting text
+//!
+//! The terminal does not report pastes as such, but with [`detect_pastes`] the prompt guesses
+//! that keys arriving in a single burst were pasted, and inserts them as is with a [`Paste`]
+//! action, rather than interpreting them key by key. Line breaks in it are handled according to
+//! the [`PastedNewlines`] policy of the [`Prompt`], and an [`Overrider`] can replace the action
+//! altogether.
+//!
+//! ```
+//! use rucline::Prompt;
+//! use rucline::actions::PastedNewlines;
+//!
+//! let prompt = Prompt::new()
+//!     .detect_pastes(true)
+//!     .pasted_newlines(PastedNewlines::Flatten);
+//! ```
+//!
+//! # Saving key binding configurations
+//!
+//! If the feature `serialize` is enabled, [`KeyBindings`] and [`ChordBindings`] can be
+//! serialized, stored, and loaded at runtime.
+//!
+//! Alternatively, the [`config`] module reads and writes [`KeyBindings`] in a human-readable
+//! notation, such as `ctrl-w = delete-word-backward`, and the [`inputrc`] module translates
+//! existing GNU readline `inputrc` files.
+//!
+//! # Default behavior
+//!
+//! In the absence of [`KeyBindings`] or an entry for a given [`Event`], the default behavior
+//! will be as follows:
+//!
+//! ```no_run
+//! # fn default_action(event: rucline::actions::Event) -> rucline::actions::Action {
+//! # use crossterm::event::{KeyCode, KeyModifiers};
+//! # use rucline::actions::{Action::*, Case::*, Direction::*, Range::*, Scope::* };
+//! # match event.code {
+//! KeyCode::Enter => Accept,
+//! KeyCode::Esc => Cancel,
+//! KeyCode::Tab => Suggest(Forward),
+//! KeyCode::BackTab => Suggest(Backward),
+//! KeyCode::Backspace => Delete(Relative(Single, Backward)),
+//! KeyCode::Delete => Delete(Relative(Single, Forward)),
+//! KeyCode::Right if event.modifiers == KeyModifiers::SHIFT => Select(Single, Forward),
+//! KeyCode::Left if event.modifiers == KeyModifiers::SHIFT => Select(Single, Backward),
+//! KeyCode::Home if event.modifiers == KeyModifiers::SHIFT => Select(Line, Backward),
+//! KeyCode::End if event.modifiers == KeyModifiers::SHIFT => Select(Line, Forward),
+//! KeyCode::Right => Move(Single, Forward),
+//! KeyCode::Left => Move(Single, Backward),
+//! KeyCode::Home => Move(Line, Backward),
+//! KeyCode::End => Move(Line, Forward),
+//! KeyCode::Char(c) => {
+//!     if event.modifiers == KeyModifiers::CONTROL {
+//!         match c {
+//!             'm' | 'd' => Accept,
+//!             'c' => Cancel,
+//!
+//!             'b' => Move(Single, Backward),
+//!             'f' => Move(Single, Forward),
+//!             'a' => Move(Line, Backward),
+//!             'e' => Move(Line, Forward),
+//!
+//!             'j' => Delete(Relative(Word, Backward)),
+//!             'k' => Delete(Relative(Word, Forward)),
+//!             'h' => Delete(Relative(Line, Backward)),
+//!             'l' => Delete(Relative(Line, Forward)),
+//!             'w' => Delete(WholeWord),
+//!             'u' => Delete(WholeLine),
+//!             't' => TransposeChars,
+//!             'x' => CutRegion, // Only while a region is selected
+//!             _ => Noop,
+//!         }
+//!     } else if event.modifiers == KeyModifiers::ALT {
+//!         match c {
+//!             'b' => Move(Word, Backward),
+//!             'f' => Move(Word, Forward),
+//!             't' => TransposeWords,
+//!             'w' => CopyRegion,
+//!             'u' => ChangeCase(Upper),
+//!             'l' => ChangeCase(Lower),
+//!             'c' => ChangeCase(Capitalize),
+//!             _ => Noop,
+//!         }
+//!     } else {
+//!         Write(c)
+//!     }
+//! }
+//! _ => Noop,
+//! # }}
+//! ```
+//!
+//! [`Prompt`]: ../prompt/struct.Prompt.html
+//! [`KeyBindings`]: type.KeyBindings.html
+//! [`ChordBindings`]: struct.ChordBindings.html
+//! [`Event`]: type.Event.html
+//! [`Action`]: enum.Action.html
+//! [`Noop`]: enum.Action.html#variant.Noop
+//! [`Sequence`]: enum.Action.html#variant.Sequence
+//! [`StartMacro`]: enum.Action.html#variant.StartMacro
+//! [`EndMacro`]: enum.Action.html#variant.EndMacro
+//! [`PlayMacro`]: enum.Action.html#variant.PlayMacro
+//! [`Keymap`]: enum.Keymap.html
+//! [`Rucline`]: enum.Keymap.html#variant.Rucline
+//! [`Emacs`]: enum.Keymap.html#variant.Emacs
+//! [`Minimal`]: enum.Keymap.html#variant.Minimal
+//! [`Vi`]: enum.Keymap.html#variant.Vi
+//! [`config`]: config/index.html
+//! [`inputrc`]: inputrc/index.html
+//! [`Paste`]: enum.Action.html#variant.Paste
+//! [`PastedNewlines`]: enum.PastedNewlines.html
+//! [`Overrider`]: trait.Overrider.html
+//! [`detect_pastes`]: ../prompt/struct.Prompt.html#method.detect_pastes
+
+mod chord;
+pub mod config;
+pub mod inputrc;
+
+pub use crate::Context;
+pub use chord::ChordBindings;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Alias to `crossterm::event::KeyEvent` from [`crossterm`](https://docs.rs/crossterm/)
+pub type Event = crossterm::event::KeyEvent;
+
+/// Alias to [`HashMap<Event, Action>`](std::collections::HashMap)
+pub type KeyBindings = std::collections::HashMap<Event, Action>;
+
+/// An action that can be performed while reading a line
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Action {
+    /// Write a single character where the cursor is, replacing the selected region, if any
+    Write(char),
+    /// Insert text where the cursor is, as is, without interpreting any of its characters.
+    /// Like [`Write`](#variant.Write), it replaces the selected region, if any
+    ///
+    /// This is the action performed when text is pasted into the terminal, according to the
+    /// [`PastedNewlines`](enum.PastedNewlines.html) policy of the prompt.
+    Paste(String),
+    /// Delete a section based on the cursor, defined by [`Scope`](enum.Scope.html)
+    Delete(Scope),
+    /// Move the cursor for a [`Range`](enum.Range.html) in a [`Direction`](enum.Direction.html)
+    Move(Range, Direction),
+    /// Trigger the [`suggester`](../completion/trait.Suggester.html)
+    Suggest(Direction),
+    /// Accept [`Range`](enum.Range.html) from the current completion presented by
+    /// [`completer`](../completion/trait.Completer.html), if any
+    Complete(Range),
+    /// Insert the text most recently deleted by a word or line [`Delete`](#variant.Delete),
+    /// or copied or cut from a region, at the cursor, replacing the selected region, if any
+    Yank,
+    /// Swap the character before the cursor with the one under it and move the cursor forward.
+    /// At the end of the line, swap the last two characters instead
+    TransposeChars,
+    /// Swap the word before the cursor with the word under or after it and move the cursor
+    /// past both. At the end of the line, swap the last two words instead
+    TransposeWords,
+    /// Change the [`Case`](enum.Case.html) from the cursor to the end of the current word and
+    /// move the cursor past it
+    ChangeCase(Case),
+    /// Read the next key as a character and move the cursor to its next or previous
+    /// occurrence, as defined by [`CharSearch`](enum.CharSearch.html). Inside a
+    /// [`Sequence`](#variant.Sequence), the character is taken from the
+    /// [`Write`](#variant.Write) that follows instead
+    MoveToChar(CharSearch),
+    /// Like [`MoveToChar`](#variant.MoveToChar), but delete the characters between the cursor
+    /// and where it would have moved
+    DeleteToChar(CharSearch),
+    /// Set the mark at the cursor, selecting the region between the mark and the cursor as
+    /// the cursor moves
+    SetMark,
+    /// Move the cursor like [`Move`](#variant.Move), extending the selected region. If there is
+    /// no mark, one is set where the cursor was, and it is removed by the next plain move
+    Select(Range, Direction),
+    /// Keep the selected region to be [`Yank`](#variant.Yank)ed back and remove the mark
+    CopyRegion,
+    /// Delete the selected region, keeping it to be [`Yank`](#variant.Yank)ed back
+    CutRegion,
+    /// Send the selected region, or the whole line if there is none, to the
+    /// [`Clipboard`](../clipboard/trait.Clipboard.html) of the prompt
+    CopyToClipboard,
+    /// Suspend the prompt and open the line in the editor set by the `VISUAL` or `EDITOR`
+    /// environment variables, replacing the line with the edited text once the editor exits
+    /// successfully. Bound to `CTRL` + `X` followed by `CTRL` + `E` in the
+    /// [`Emacs`](enum.Keymap.html#variant.Emacs) keymap, and to any key with
+    /// [`ChordBindings`](struct.ChordBindings.html)
+    OpenEditor,
+    /// Accept the current line
+    Accept,
+    /// Cancel the suggestions, if any. Else, discard the whole line
+    Cancel,
+    /// Perform each action in order, e.g. to bind a single [`Event`](type.Event.html) to a
+    /// list of actions. An [`Accept`](#variant.Accept) or a [`Cancel`](#variant.Cancel) that
+    /// ends the prompt stops the sequence
+    Sequence(Vec<Action>),
+    /// Start recording the actions performed into a keyboard macro, discarding the previously
+    /// recorded one
+    StartMacro,
+    /// Stop recording the keyboard macro
+    EndMacro,
+    /// Perform the actions of the last recorded keyboard macro
+    PlayMacro,
+    /// Do nothing and wait for the next [`Event`](type.Event.html)
+    Noop,
+}
+
+/// The scope an [`Action`](enum.Action.html) should be applied on
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Scope {
+    /// Represents a whole line
+    WholeLine,
+    /// Represents a whole word
+    WholeWord,
+    /// Represents a relative scope, with a [`Range`](enum.Range.html)
+    /// and [`Direction`](enum.Direction.html)
+    Relative(Range, Direction),
+}
+
+/// The range an [`Action`](enum.Action.html) should extend for
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Range {
+    /// Represents the remainder of the line
+    Line,
+    /// Represents a single word, as delimited by the [`WordBoundary`](enum.WordBoundary.html)
+    /// of the prompt
+    Word,
+    /// Represents a single character
+    Single,
+}
+
+/// Defines where words start and end for every [`Action`](enum.Action.html) on a
+/// [`Word`](enum.Range.html#variant.Word), including the vi motions.
+///
+/// # Example
+///
+/// ```
+/// use rucline::Prompt;
+/// use rucline::actions::WordBoundary;
+///
+/// // Move through `/usr/local/bin` one path segment at a time
+/// let prompt = Prompt::new().word_boundary(WordBoundary::Punctuation);
+///
+/// // Only split words on the separators of a configuration
+/// let separators = vec![',', ';'];
+/// let words = WordBoundary::custom(move |c| separators.contains(&c));
+/// let prompt = Prompt::new().word_boundary(words);
+/// ```
+#[derive(Default, Clone)]
+pub enum WordBoundary {
+    /// Words are separated by whitespace only
+    #[default]
+    Whitespace,
+    /// Words are separated by whitespace and punctuation, except for `_`
+    ///
+    /// Punctuation is any character that is neither alphanumeric, whitespace nor a combining
+    /// mark, so symbols such as `+` and non-ASCII punctuation such as `«` or `—` also separate
+    /// words.
+    Punctuation,
+    /// Words are separated by whitespace and punctuation, and split at `camelCase` humps, so
+    /// that `snake_case` and `camelCase` identifiers are traversed one part at a time
+    Subword,
+    /// Words are separated by the characters for which the function returns `true`
+    Custom(std::rc::Rc<dyn Fn(char) -> bool>),
+}
+
+impl WordBoundary {
+    /// Creates a [`Custom`](#variant.Custom) boundary from a function that returns `true` for
+    /// the characters that separate words.
+    pub fn custom(is_separator: impl Fn(char) -> bool + 'static) -> Self {
+        WordBoundary::Custom(std::rc::Rc::new(is_separator))
+    }
+
+    /// Returns `true` if `c` is not part of any word.
+    pub(crate) fn is_separator(&self, c: char) -> bool {
+        match self {
+            WordBoundary::Whitespace => c.is_whitespace(),
+            WordBoundary::Punctuation => c.is_whitespace() || (is_punctuation(c) && c != '_'),
+            WordBoundary::Subword => c.is_whitespace() || is_punctuation(c),
+            WordBoundary::Custom(is_separator) => is_separator(c),
+        }
+    }
+
+    /// Returns `true` if a new word starts at `next`, even though neither `previous` nor
+    /// `next` are separators.
+    pub(crate) fn splits(&self, previous: char, next: char) -> bool {
+        match self {
+            WordBoundary::Subword => {
+                (previous.is_lowercase() || previous.is_numeric()) && next.is_uppercase()
+            }
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Debug for WordBoundary {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WordBoundary::Whitespace => fmt.write_str("Whitespace"),
+            WordBoundary::Punctuation => fmt.write_str("Punctuation"),
+            WordBoundary::Subword => fmt.write_str("Subword"),
+            WordBoundary::Custom(_) => fmt.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// Returns `true` if `c` is punctuation or a symbol, in any script.
+fn is_punctuation(c: char) -> bool {
+    !c.is_alphanumeric() && !c.is_whitespace() && !unicode_normalization::char::is_combining_mark(c)
+}
+
+/// Where a [`MoveToChar`](enum.Action.html#variant.MoveToChar) or a
+/// [`DeleteToChar`](enum.Action.html#variant.DeleteToChar) action stops
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CharSearch {
+    /// Stops on the character found in the [`Direction`](enum.Direction.html), like vi's `f`
+    /// and `F`
+    Find(Direction),
+    /// Stops next to the character found in the [`Direction`](enum.Direction.html), like vi's
+    /// `t` and `T`
+    Till(Direction),
+}
+
+/// The case a [`ChangeCase`](enum.Action.html#variant.ChangeCase) action converts to
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Case {
+    /// Represents upper case, e.g. "WORD"
+    Upper,
+    /// Represents lower case, e.g. "word"
+    Lower,
+    /// Represents an upper case first letter followed by lower case, e.g. "Word"
+    Capitalize,
+}
+
+/// How line breaks in pasted text are handled by the [`Prompt`]
+///
+/// [`Prompt`]: ../prompt/struct.Prompt.html
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PastedNewlines {
+    /// Inserts the line breaks into the line, as any other character
+    #[default]
+    Keep,
+    /// Drops trailing line breaks and replaces the remaining ones with a space
+    Flatten,
+    /// Ignores the whole paste if it contains any line break
+    Reject,
+}
+
+impl PastedNewlines {
+    fn apply(self, text: String) -> Option<String> {
+        let is_break = |c| c == '\n' || c == '\r';
+        match self {
+            Self::Keep => Some(text),
+            Self::Flatten => Some(
+                text.trim_end_matches(is_break)
+                    .split(is_break)
+                    .filter(|line| !line.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
+            Self::Reject => {
+                if text.contains(is_break) {
+                    None
+                } else {
+                    Some(text)
+                }
+            }
+        }
+    }
+}
+
+/// The direction an [`Action`](enum.Action.html) may take
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Direction {
+    /// Represents a "left" or "down" direction
+    Forward,
+    /// Represents a "right" or "up" direction
+    Backward,
+}
+
+/// The set of key bindings used by the [`Prompt`] in the absence of an override.
+///
+/// [`Prompt`]: ../prompt/struct.Prompt.html
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Keymap {
+    /// The default rucline behavior, as described in the [module](index.html#default-behavior)
+    #[default]
+    Rucline,
+    /// A vi-like modal keymap.
+    ///
+    /// The prompt starts in insert mode, where the [`Rucline`](#variant.Rucline) behavior
+    /// applies, and `Esc` switches to normal mode. Normal mode supports the motions `h`, `l`,
+    /// `w`, `b`, `e`, `0`, `$`, `f{c}`, `F{c}`, `t{c}` and `T{c}`, the operators `d`, `c` and
+    /// `y` combined with motions and counts, as well as `x`, `X`, `D`, `C`, `s`, `p`, `P`, `i`,
+    /// `a`, `I`, `A` and the `.` repeat. A mode indicator is printed before the prompt text.
+    Vi,
+    /// The default bindings of GNU readline's emacs mode.
+    ///
+    /// On top of the arrows, `Home`, `End`, `Tab` and `Enter`:
+    ///
+    /// | Key                               | Action                                             |
+    /// |-----------------------------------|----------------------------------------------------|
+    /// | `CTRL` + `A` / `E`                | Move to the start / end of the line                |
+    /// | `CTRL` + `B` / `F`                | Move one character backward / forward              |
+    /// | `ALT` + `B` / `F`                 | Move one word backward / forward                   |
+    /// | `CTRL` + `H`, `Backspace`         | Delete the previous character                      |
+    /// | `CTRL` + `D`, `Delete`            | Delete the next character, or cancel an empty line |
+    /// | `CTRL` + `W`, `ALT` + `Backspace` | Delete the previous word                           |
+    /// | `ALT` + `D`                       | Delete the next word                               |
+    /// | `CTRL` + `U` / `K`                | Delete to the start / end of the line              |
+    /// | `CTRL` + `Y`                      | [`Yank`] the last deleted text                     |
+    /// | `CTRL` + `Space`                  | [`SetMark`]                                        |
+    /// | `ALT` + `W`                       | [`CopyRegion`]                                     |
+    /// | `CTRL` + `T`                      | [`TransposeChars`]                                 |
+    /// | `ALT` + `T`                       | [`TransposeWords`]                                 |
+    /// | `ALT` + `U` / `L` / `C`           | Upper case / lower case / capitalize the word      |
+    /// | `CTRL` + `X`, `CTRL` + `E`        | [`OpenEditor`]                                     |
+    /// | `CTRL` + `]`                      | Move forward to the next key typed                 |
+    /// | `CTRL` + `ALT` + `]`              | Move backward to the next key typed                |
+    /// | `CTRL` + `J` / `M`                | Accept the line                                    |
+    /// | `CTRL` + `C` / `G`, `Esc`         | Cancel                                             |
+    ///
+    /// With a selected region, `CTRL` + `W` cuts it instead of deleting the previous word.
+    ///
+    /// [`Yank`]: enum.Action.html#variant.Yank
+    /// [`SetMark`]: enum.Action.html#variant.SetMark
+    /// [`CopyRegion`]: enum.Action.html#variant.CopyRegion
+    /// [`TransposeChars`]: enum.Action.html#variant.TransposeChars
+    /// [`TransposeWords`]: enum.Action.html#variant.TransposeWords
+    /// [`OpenEditor`]: enum.Action.html#variant.OpenEditor
+    Emacs,
+    /// Only the essentials: writing, `Backspace`, `Delete`, the arrows, `Home`, `End`, `Tab`
+    /// and `Shift` + `Tab` for suggestions, `Enter` to accept and `Esc` or `CTRL` + `C` to
+    /// cancel.
+    Minimal,
+}
+
+/// Overrides the behavior for a given [`Event`].
+///
+/// This trait has a convenience implementation for [`KeyBindings`] and also a conversion
+/// from lambdas.
+///
+/// # Example
+///
+/// ```
+/// use rucline::Prompt;
+/// use rucline::actions::{Action, Context, Event};
+/// use crossterm::event::KeyCode;
+///
+/// let prompt = Prompt::new().overrider(|e, _: &dyn Context| if e == Event::from(KeyCode::Tab) {
+///     Some(Action::Write('\t'))
+/// } else {
+///     None
+/// });
+/// ```
+///
+/// [`Event`]: type.Event.html
+/// [`KeyBindings`]: type.KeyBindings.html
+pub trait Overrider {
+    /// Overrides the behavior for the given [`Event`].
+    ///
+    /// [`Context`] will contain the current context of the prompt, in case the behavior should
+    /// be contextual.
+    ///
+    /// # Arguments
+    /// * [`event`] - The incoming event to be processed.
+    /// * [`context`] - The current context in which this event is coming in.
+    ///
+    /// [`Event`]: type.Event.html
+    /// [`Context`]: ../prompt/context/trait.Context.html
+    fn override_for(&self, event: Event, context: &dyn Context) -> Option<Action>;
+
+    /// Overrides the behavior for text pasted into the prompt.
+    ///
+    /// By default, the text is inserted with a [`Paste`] action, after the
+    /// [`PastedNewlines`] policy of the prompt is applied.
+    ///
+    /// # Arguments
+    /// * `text` - The text being pasted, before any line breaks are handled.
+    /// * `context` - The current context in which this text is coming in.
+    ///
+    /// [`Paste`]: enum.Action.html#variant.Paste
+    /// [`PastedNewlines`]: enum.PastedNewlines.html
+    fn override_paste(&self, text: &str, context: &dyn Context) -> Option<Action> {
+        let _ = (text, context);
+        None
+    }
+
+    /// How long the prompt should wait for the next event before calling
+    /// [`override_timeout`], if at all.
+    ///
+    /// This lets an overrider that buffers events, such as [`ChordBindings`], act on them even
+    /// if no other event follows. By default, the prompt waits indefinitely.
+    ///
+    /// [`ChordBindings`]: struct.ChordBindings.html
+    /// [`override_timeout`]: trait.Overrider.html#method.override_timeout
+    fn pending_timeout(&self) -> Option<std::time::Duration> {
+        None
+    }
+
+    /// Provides the behavior for when no event arrived within the [`pending_timeout`].
+    ///
+    /// # Arguments
+    /// * `context` - The current context of the prompt.
+    ///
+    /// [`pending_timeout`]: trait.Overrider.html#method.pending_timeout
+    fn override_timeout(&self, context: &dyn Context) -> Option<Action> {
+        let _ = context;
+        None
+    }
+}
+
+impl Overrider for KeyBindings {
+    fn override_for(&self, event: Event, _: &dyn Context) -> Option<Action> {
+        self.get(&event).cloned()
+    }
+}
+
+impl<F> Overrider for F
+where
+    F: Fn(Event, &dyn Context) -> Option<Action>,
+{
+    fn override_for(&self, event: Event, context: &dyn Context) -> Option<Action> {
+        self(event, context)
+    }
+}
+
+// Allowed because the prompt stores its overrider as an `Option<Box<_>>`
+#[allow(clippy::ref_option)]
+pub(super) fn action_for(
+    overrides: &Option<Box<dyn Overrider>>,
+    chords: Option<&ChordBindings>,
+    keymap: Keymap,
+    event: Event,
+    context: &impl Context,
+) -> Action {
+    if let Some(action) = overrides
+        .as_ref()
+        .and_then(|b| b.override_for(event, context))
+        .or_else(|| chords.and_then(|chords| chords.override_for(event, context)))
+    {
+        action
+    } else {
+        keymap_action(keymap, event, context)
+    }
+}
+
+/// The sequences of keys bound by the `keymap`, if any, to be passed to [`action_for`].
+pub(super) fn keymap_chords(keymap: Keymap) -> Option<ChordBindings> {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    match keymap {
+        Keymap::Emacs => {
+            let control = |c| Event::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+            let mut chords = ChordBindings::new().keymap(keymap);
+            chords.insert(&[control('x'), control('e')], Action::OpenEditor);
+            Some(chords)
+        }
+        Keymap::Rucline | Keymap::Vi | Keymap::Minimal => None,
+    }
+}
+
+/// The action of the `keymap` for the `event`, regardless of any overrides.
+fn keymap_action(keymap: Keymap, event: Event, context: &dyn Context) -> Action {
+    match keymap {
+        // The vi normal mode is handled by the prompt, insert mode behaves as rucline
+        Keymap::Rucline | Keymap::Vi => default_action(event, context),
+        Keymap::Emacs => emacs_action(event, context),
+        Keymap::Minimal => minimal_action(event),
+    }
+}
+
+// Allowed because the prompt stores its overrider as an `Option<Box<_>>`
+#[allow(clippy::ref_option)]
+pub(super) fn paste_action(
+    overrides: &Option<Box<dyn Overrider>>,
+    newlines: PastedNewlines,
+    text: String,
+    context: &impl Context,
+) -> Action {
+    if let Some(action) = overrides
+        .as_ref()
+        .and_then(|b| b.override_paste(&text, context))
+    {
+        action
+    } else {
+        newlines.apply(text).map_or(Action::Noop, Action::Paste)
+    }
+}
+
+#[inline]
+fn control_pressed(event: &Event) -> bool {
+    event.modifiers == crossterm::event::KeyModifiers::CONTROL
+}
+
+#[inline]
+fn alt_pressed(event: &Event) -> bool {
+    event.modifiers == crossterm::event::KeyModifiers::ALT
+}
+
+#[inline]
+fn shift_pressed(event: &Event) -> bool {
+    event.modifiers == crossterm::event::KeyModifiers::SHIFT
+}
+
+#[inline]
+fn complete_if_at_end_else_move(context: &dyn Context, range: Range) -> Action {
+    if context.cursor() == context.buffer().len() {
+        if range == Range::Word {
+            Action::Complete(Range::Word)
+        } else {
+            Action::Complete(Range::Line)
+        }
+    } else {
+        Action::Move(range, Direction::Forward)
+    }
+}
+
+// TODO: Investigate '\n' being parsed and 'ENTER'
+fn default_action(event: Event, context: &dyn Context) -> Action {
+    use crossterm::event::KeyCode;
+    use Action::{
+        Accept, Cancel, ChangeCase, CopyRegion, CutRegion, Delete, Move, Noop, Select, Suggest,
+        TransposeChars, TransposeWords, Write,
+    };
+    use Direction::{Backward, Forward};
+    use Range::{Line, Single, Word};
+    use Scope::{Relative, WholeLine, WholeWord};
+
+    match event.code {
+        KeyCode::Enter => Accept,
+        KeyCode::Esc => Cancel,
+        KeyCode::Tab => Suggest(Forward),
+        KeyCode::BackTab => Suggest(Backward),
+        KeyCode::Backspace => Delete(Relative(Single, Backward)),
+        KeyCode::Delete => Delete(Relative(Single, Forward)),
+        KeyCode::Right if shift_pressed(&event) => Select(Single, Forward),
+        KeyCode::Left if shift_pressed(&event) => Select(Single, Backward),
+        KeyCode::Home if shift_pressed(&event) => Select(Line, Backward),
+        KeyCode::End if shift_pressed(&event) => Select(Line, Forward),
+        KeyCode::Right => complete_if_at_end_else_move(context, Single),
+        KeyCode::Left => Move(Single, Backward),
+        KeyCode::Home => Move(Line, Backward),
+        KeyCode::End => complete_if_at_end_else_move(context, Line),
+        KeyCode::Char(c) => {
+            if control_pressed(&event) {
+                match c {
+                    'm' | 'd' => Accept,
+                    'c' => Cancel,
+
+                    'b' => Move(Single, Backward),
+                    'f' => complete_if_at_end_else_move(context, Single),
+                    'a' => Move(Line, Backward),
+                    'e' => complete_if_at_end_else_move(context, Line),
+
+                    'j' => Delete(Relative(Word, Backward)),
+                    'k' => Delete(Relative(Word, Forward)),
+                    'h' => Delete(Relative(Line, Backward)),
+                    'l' => Delete(Relative(Line, Forward)),
+                    'w' => Delete(WholeWord),
+                    'u' => Delete(WholeLine),
+                    't' => TransposeChars,
+                    // Left alone otherwise, as it usually starts a chord
+                    'x' if context.selection().is_some() => CutRegion,
+                    _ => Noop,
+                }
+            } else if alt_pressed(&event) {
+                match c {
+                    'b' => Move(Word, Backward),
+                    'f' => complete_if_at_end_else_move(context, Word),
+                    't' => TransposeWords,
+                    'w' => CopyRegion,
+                    'u' => ChangeCase(Case::Upper),
+                    'l' => ChangeCase(Case::Lower),
+                    'c' => ChangeCase(Case::Capitalize),
+                    _ => Noop,
+                }
+            } else {
+                Write(c)
+            }
+        }
+        _ => Noop,
+    }
+}
+
+fn emacs_action(event: Event, context: &dyn Context) -> Action {
+    use crossterm::event::KeyCode;
+    use Action::{
+        Accept, Cancel, ChangeCase, CopyRegion, CutRegion, Delete, Move, MoveToChar, Noop, SetMark,
+        Suggest, TransposeChars, TransposeWords, Write, Yank,
+    };
+    use Direction::{Backward, Forward};
+    use Range::{Line, Single, Word};
+    use Scope::Relative;
+
+    // Depending on the terminal, `CTRL` + `]` is reported as `CTRL` + `5`
+    let control_alt = crossterm::event::KeyModifiers::CONTROL | crossterm::event::KeyModifiers::ALT;
+    if let KeyCode::Char(']' | '5') = event.code {
+        if control_pressed(&event) {
+            return MoveToChar(CharSearch::Find(Forward));
+        } else if event.modifiers == control_alt {
+            return MoveToChar(CharSearch::Find(Backward));
+        }
+    }
+
+    match event.code {
+        KeyCode::Enter => Accept,
+        KeyCode::Esc => Cancel,
+        KeyCode::Tab => Suggest(Forward),
+        KeyCode::BackTab => Suggest(Backward),
+        KeyCode::Backspace if alt_pressed(&event) => Delete(Relative(Word, Backward)),
+        KeyCode::Backspace => Delete(Relative(Single, Backward)),
+        KeyCode::Delete => Delete(Relative(Single, Forward)),
+        KeyCode::Right => complete_if_at_end_else_move(context, Single),
+        KeyCode::Left => Move(Single, Backward),
+        KeyCode::Home => Move(Line, Backward),
+        KeyCode::End => complete_if_at_end_else_move(context, Line),
+        KeyCode::Char(c) => {
+            if control_pressed(&event) {
+                match c {
+                    'm' | 'j' => Accept,
+                    'c' | 'g' => Cancel,
+                    'd' if context.buffer().is_empty() => Cancel,
+
+                    'b' => Move(Single, Backward),
+                    'f' => complete_if_at_end_else_move(context, Single),
+                    'a' => Move(Line, Backward),
+                    'e' => complete_if_at_end_else_move(context, Line),
+
+                    ' ' => SetMark,
+                    'h' => Delete(Relative(Single, Backward)),
+                    'd' => Delete(Relative(Single, Forward)),
+                    'w' if context.selection().is_some() => CutRegion,
+                    'w' => Delete(Relative(Word, Backward)),
+                    'u' => Delete(Relative(Line, Backward)),
+                    'k' => Delete(Relative(Line, Forward)),
+                    'y' => Yank,
+                    't' => TransposeChars,
+                    _ => Noop,
+                }
+            } else if alt_pressed(&event) {
+                match c {
+                    'b' => Move(Word, Backward),
+                    'f' => complete_if_at_end_else_move(context, Word),
+                    'd' => Delete(Relative(Word, Forward)),
+                    'w' => CopyRegion,
+                    't' => TransposeWords,
+                    'u' => ChangeCase(Case::Upper),
+                    'l' => ChangeCase(Case::Lower),
+                    'c' => ChangeCase(Case::Capitalize),
+                    _ => Noop,
+                }
+            } else {
+                Write(c)
+            }
+        }
+        _ => Noop,
+    }
+}
+
+fn minimal_action(event: Event) -> Action {
+    use crossterm::event::KeyCode;
+    use Action::{Accept, Cancel, Delete, Move, Noop, Suggest, Write};
+    use Direction::{Backward, Forward};
+    use Range::{Line, Single};
+    use Scope::Relative;
+
+    match event.code {
+        KeyCode::Enter => Accept,
+        KeyCode::Esc => Cancel,
+        KeyCode::Tab => Suggest(Forward),
+        KeyCode::BackTab => Suggest(Backward),
+        KeyCode::Backspace => Delete(Relative(Single, Backward)),
+        KeyCode::Delete => Delete(Relative(Single, Forward)),
+        KeyCode::Right => Move(Single, Forward),
+        KeyCode::Left => Move(Single, Backward),
+        KeyCode::Home => Move(Line, Backward),
+        KeyCode::End => Move(Line, Forward),
+        KeyCode::Char('c') if control_pressed(&event) => Cancel,
+        KeyCode::Char(c) if !control_pressed(&event) && !alt_pressed(&event) => Write(c),
+        _ => Noop,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        action_for, default_action, keymap_chords, paste_action, Action, Direction, Event, Keymap,
+        PastedNewlines, Range, Scope,
+    };
+    use crate::test::mock::Context as Mock;
+
+    #[test]
+    fn should_complete_if_at_end() {
+        use crossterm::event::KeyCode::{Char, End, Right};
+        use crossterm::event::KeyModifiers;
+        use Action::{Complete, Move};
+        use Direction::Forward;
+        use Range::{Line, Single, Word};
+
+        let mut c = Mock::from("a");
+
+        assert_eq!(default_action(Event::from(Right), &c), Complete(Line));
+        assert_eq!(default_action(Event::from(End), &c), Complete(Line));
+        assert_eq!(
+            default_action(Event::new(Char('f'), KeyModifiers::CONTROL), &c),
+            Complete(Line)
+        );
+        assert_eq!(
+            default_action(Event::new(Char('f'), KeyModifiers::ALT), &c),
+            Complete(Word)
+        );
+
+        c.cursor = 0;
+
+        assert_eq!(
+            default_action(Event::from(Right), &c),
+            Move(Single, Forward)
+        );
+        assert_eq!(default_action(Event::from(End), &c), Move(Line, Forward));
+        assert_eq!(
+            default_action(Event::new(Char('f'), KeyModifiers::CONTROL), &c),
+            Move(Single, Forward)
+        );
+        assert_eq!(
+            default_action(Event::new(Char('f'), KeyModifiers::ALT), &c),
+            Move(Word, Forward)
+        );
+    }
+
+    #[test]
+    fn cut_only_a_selected_region() {
+        use crossterm::event::{KeyCode::Char, KeyModifiers};
+
+        struct Selected(Mock);
+        impl crate::Context for Selected {
+            fn buffer(&self) -> &[char] {
+                self.0.buffer()
+            }
+            fn cursor(&self) -> usize {
+                self.0.cursor()
+            }
+            fn selection(&self) -> Option<&[char]> {
+                Some(&self.0.buffer()[..1])
+            }
+        }
+
+        let control_x = Event::new(Char('x'), KeyModifiers::CONTROL);
+        assert_eq!(default_action(control_x, &Mock::from("ab")), Action::Noop);
+        assert_eq!(
+            default_action(control_x, &Selected(Mock::from("ab"))),
+            Action::CutRegion
+        );
+    }
+
+    #[test]
+    fn should_default_if_no_mapping() {
+        use crossterm::event::KeyCode::Tab;
+        let action = action_for(
+            &None,
+            None,
+            Keymap::Rucline,
+            Event::from(Tab),
+            &Mock::empty(),
+        );
+        assert_eq!(action, Action::Suggest(Direction::Forward));
+    }
+
+    #[test]
+    fn should_follow_keymap() {
+        use crossterm::event::KeyCode::{Backspace, Char};
+        use crossterm::event::KeyModifiers;
+
+        let context = Mock::from("abc");
+        let control_k = Event::new(Char('k'), KeyModifiers::CONTROL);
+        let alt_backspace = Event::new(Backspace, KeyModifiers::ALT);
+
+        assert_eq!(
+            action_for(&None, None, Keymap::Rucline, control_k, &context),
+            Action::Delete(Scope::Relative(Range::Word, Direction::Forward))
+        );
+        assert_eq!(
+            action_for(&None, None, Keymap::Emacs, control_k, &context),
+            Action::Delete(Scope::Relative(Range::Line, Direction::Forward))
+        );
+        assert_eq!(
+            action_for(&None, None, Keymap::Emacs, alt_backspace, &context),
+            Action::Delete(Scope::Relative(Range::Word, Direction::Backward))
+        );
+        assert_eq!(
+            action_for(&None, None, Keymap::Minimal, control_k, &context),
+            Action::Noop
+        );
+
+        // `CTRL` + `D` only cancels an empty line
+        let control_d = Event::new(Char('d'), KeyModifiers::CONTROL);
+        assert_eq!(
+            action_for(&None, None, Keymap::Emacs, control_d, &context),
+            Action::Delete(Scope::Relative(Range::Single, Direction::Forward))
+        );
+        assert_eq!(
+            action_for(&None, None, Keymap::Emacs, control_d, &Mock::empty()),
+            Action::Cancel
+        );
+    }
+
+    #[test]
+    fn open_editor_with_emacs_chord() {
+        use crossterm::event::{KeyCode::Char, KeyModifiers};
+
+        let control_x = Event::new(Char('x'), KeyModifiers::CONTROL);
+        let control_e = Event::new(Char('e'), KeyModifiers::CONTROL);
+        let chords = keymap_chords(Keymap::Emacs);
+        let action =
+            |event| action_for(&None, chords.as_ref(), Keymap::Emacs, event, &Mock::empty());
+
+        assert_eq!(action(control_x), Action::Noop);
+        assert_eq!(action(control_e), Action::OpenEditor);
+        assert_eq!(action(control_e), Action::Complete(Range::Line));
+        assert!(keymap_chords(Keymap::Rucline).is_none());
+    }
+
+    #[test]
+    fn should_select_with_shift() {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        let c = Mock::from("abc");
+        let shift = |code| Event::new(code, KeyModifiers::SHIFT);
+
+        assert_eq!(
+            default_action(shift(KeyCode::Left), &c),
+            Action::Select(Range::Single, Direction::Backward)
+        );
+        assert_eq!(
+            default_action(shift(KeyCode::End), &c),
+            Action::Select(Range::Line, Direction::Forward)
+        );
+        assert_eq!(
+            default_action(Event::from(KeyCode::Left), &c),
+            Action::Move(Range::Single, Direction::Backward)
+        );
+    }
+
+    #[test]
+    fn should_apply_newline_policy_to_pastes() {
+        let paste = |newlines, text: &str| {
+            paste_action(&None, newlines, String::from(text), &Mock::empty())
+        };
+
+        assert_eq!(
+            paste(PastedNewlines::Keep, "a\nb\n"),
+            Action::Paste(String::from("a\nb\n"))
+        );
+        assert_eq!(
+            paste(PastedNewlines::Flatten, "a\nb\r\n\nc\n"),
+            Action::Paste(String::from("a b c"))
+        );
+        assert_eq!(paste(PastedNewlines::Reject, "a\nb"), Action::Noop);
+        assert_eq!(
+            paste(PastedNewlines::Reject, "a b"),
+            Action::Paste(String::from("a b"))
+        );
+    }
+
+    mod basic {
+        use super::super::{action_for, Action, Direction, Event, KeyBindings, Keymap};
+        use super::Mock;
+        use crossterm::event::KeyCode::Tab;
+
+        #[test]
+        fn should_default_if_event_missing_form_mapping() {
+            let overrider = Box::new(KeyBindings::new());
+            let action = action_for(
+                &Some(overrider),
+                None,
+                Keymap::Rucline,
+                Event::from(Tab),
+                &Mock::empty(),
+            );
+            assert_eq!(action, Action::Suggest(Direction::Forward));
+        }
+
+        #[test]
+        fn should_override_if_defined() {
+            let mut bindings = KeyBindings::new();
+            bindings.insert(Event::from(Tab), Action::Write('\t'));
+            let overrider = Box::new(bindings);
+            let action = action_for(
+                &Some(overrider),
+                None,
+                Keymap::Rucline,
+                Event::from(Tab),
+                &Mock::empty(),
+            );
+            assert_eq!(action, Action::Write('\t'));
+        }
+
+        #[test]
+        fn should_override_with_sequences() {
+            let sequence = Action::Sequence(vec![Action::Write('\t'), Action::Accept]);
+            let mut bindings = KeyBindings::new();
+            bindings.insert(Event::from(Tab), sequence.clone());
+            let overrider = Box::new(bindings);
+            let action = action_for(
+                &Some(overrider),
+                None,
+                Keymap::Rucline,
+                Event::from(Tab),
+                &Mock::empty(),
+            );
+            assert_eq!(action, sequence);
+        }
+    }
+
+    mod lambda {
+        use super::super::{action_for, Action, Context, Direction, Event, Keymap};
+        use super::Mock;
+        use crossterm::event::KeyCode::Tab;
+
+        #[test]
+        fn should_default_if_event_missing_form_mapping() {
+            let overrider = Box::new(|_, _: &dyn Context| None);
+            let action = action_for(
+                &Some(overrider),
+                None,
+                Keymap::Rucline,
+                Event::from(Tab),
+                &Mock::empty(),
+            );
+            assert_eq!(action, Action::Suggest(Direction::Forward));
+        }
+
+        #[test]
+        fn should_override_if_defined() {
+            let overrider = Box::new(|e, _: &dyn Context| {
+                if e == Event::from(Tab) {
+                    Some(Action::Write('\t'))
+                } else {
+                    None
+                }
+            });
+            let action = action_for(
+                &Some(overrider),
+                None,
+                Keymap::Rucline,
+                Event::from(Tab),
+                &Mock::empty(),
+            );
+            assert_eq!(action, Action::Write('\t'));
+        }
+    }
+}
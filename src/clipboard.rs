@@ -0,0 +1,121 @@
+//! Provides the [`Clipboard`] that receives the text copied by the [`CopyToClipboard`] action.
+//!
+//! By default, the [`Prompt`] uses [`Osc52`], which asks the terminal itself to place the text
+//! in the clipboard. Since the request travels with the output of the program, it reaches the
+//! local clipboard even when the prompt runs on a remote machine, e.g. over SSH.
+//!
+//! Any other backend can be plugged in by implementing [`Clipboard`], or with a closure:
+//!
+//! ```
+//! use rucline::Prompt;
+//!
+//! let prompt = Prompt::new().clipboard(|text: &str| {
+//!     println!("Copied {}", text);
+//!     Ok(())
+//! });
+//! ```
+//!
+//! [`Clipboard`]: trait.Clipboard.html
+//! [`Osc52`]: struct.Osc52.html
+//! [`CopyToClipboard`]: ../actions/enum.Action.html#variant.CopyToClipboard
+//! [`Prompt`]: ../prompt/struct.Prompt.html
+
+/// A destination for copied text.
+pub trait Clipboard {
+    /// Places `text` in the clipboard.
+    ///
+    /// # Errors
+    /// * [`ErrorKind`] - If the text could not be delivered to the clipboard.
+    ///
+    /// [`ErrorKind`]: ../enum.ErrorKind.html
+    fn copy(&self, text: &str) -> Result<(), crate::ErrorKind>;
+}
+
+impl<F> Clipboard for F
+where
+    F: Fn(&str) -> Result<(), crate::ErrorKind>,
+{
+    fn copy(&self, text: &str) -> Result<(), crate::ErrorKind> {
+        self(text)
+    }
+}
+
+/// Copies text with the OSC 52 escape sequence, which is supported by most modern terminal
+/// emulators.
+///
+/// Some terminals, and multiplexers such as `tmux`, require this to be enabled in their
+/// configuration. Terminals without support simply ignore the sequence.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Osc52;
+
+impl Clipboard for Osc52 {
+    fn copy(&self, text: &str) -> Result<(), crate::ErrorKind> {
+        use std::io::Write;
+
+        crossterm::execute!(std::io::stdout(), crossterm::style::Print(sequence(text)))
+    }
+}
+
+/// Builds the OSC 52 sequence that sets the clipboard to `text`.
+fn sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", encode(text.as_bytes()))
+}
+
+/// Encodes `bytes` in the standard, padded, base64 alphabet.
+fn encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0_u32, |group, (index, byte)| {
+                group | u32::from(*byte) << (16 - 8 * index)
+            });
+
+        for index in 0..4 {
+            if index <= chunk.len() {
+                let sextet = (group >> (18 - 6 * index)) & 0x3f;
+                encoded.push(char::from(ALPHABET[sextet as usize]));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod test {
+    use super::{encode, sequence, Clipboard};
+
+    #[test]
+    fn encode_base64() {
+        assert_eq!(encode(b""), "");
+        assert_eq!(encode(b"f"), "Zg==");
+        assert_eq!(encode(b"fo"), "Zm8=");
+        assert_eq!(encode(b"foo"), "Zm9v");
+        assert_eq!(encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(encode("ação".as_bytes()), "YcOnw6Nv");
+    }
+
+    #[test]
+    fn build_sequence() {
+        assert_eq!(sequence("foo"), "\x1b]52;c;Zm9v\x07");
+    }
+
+    #[test]
+    fn closures_are_clipboards() {
+        let copied = std::cell::RefCell::new(String::new());
+        let clipboard = |text: &str| {
+            copied.replace(String::from(text));
+            Ok(())
+        };
+
+        clipboard.copy("text").unwrap();
+        assert_eq!(copied.into_inner(), "text");
+    }
+}
@@ -0,0 +1,599 @@
+use super::{Basic, Candidate, Completer, Context, Suggester};
+use std::borrow::Cow;
+
+/// A completer and suggester for command lines made of nested commands, flags and arguments,
+/// e.g. `db connect <host>` or `db list --verbose`.
+///
+/// The buffer is split into words as described in [`tokenize`]. Only what is valid at the word
+/// under the cursor is offered:
+/// * Before any argument, the subcommands of the current command.
+/// * When the word starts with `-`, the flags of the current command not given yet.
+/// * After a flag that takes a value, or at the position of an argument, whatever the
+///   [`Argument`] offers.
+///
+/// Subcommands and flags are suggested along with their descriptions, if any, and followed by
+/// a space once taken. Words that are not recognized are skipped, so the rest of the line is
+/// still completed.
+///
+/// # Example
+///
+/// ```
+/// use rucline::completion::{Argument, Command, CommandTree, Flag, Paths};
+///
+/// let tree = CommandTree::new()
+///     .command(
+///         Command::new("db")
+///             .subcommand(
+///                 Command::new("connect")
+///                     .description("open a session")
+///                     .argument(Argument::choices(&["localhost", "staging", "production"])),
+///             )
+///             .subcommand(
+///                 Command::new("list")
+///                     .description("list the tables")
+///                     .flag(Flag::new("--verbose").alias("-v").description("show the columns"))
+///                     .flag(Flag::new("--output").value(Argument::new().suggester(Paths::new()))),
+///             ),
+///     )
+///     .command(Command::new("quit"));
+/// ```
+///
+/// [`Argument`]: struct.Argument.html
+/// [`tokenize`]: ../context/fn.tokenize.html
+#[derive(Default)]
+pub struct CommandTree(Command);
+
+/// A command of a [`CommandTree`](struct.CommandTree.html), along with the subcommands, flags
+/// and positional arguments that may follow it.
+#[derive(Default)]
+pub struct Command {
+    name: Vec<char>,
+    description: Option<Vec<char>>,
+    subcommands: Vec<Command>,
+    flags: Vec<Flag>,
+    arguments: Vec<Argument>,
+}
+
+/// A flag of a [`Command`](struct.Command.html), e.g. `--verbose`, which may take a value.
+pub struct Flag {
+    names: Vec<Vec<char>>,
+    description: Option<Vec<char>>,
+    value: Option<Argument>,
+}
+
+/// A positional argument of a [`Command`], or the value of a [`Flag`].
+///
+/// Without a [`completer`] or a [`suggester`], the argument is free text and nothing is
+/// offered for it.
+///
+/// The completer and suggester plugged in only see the word of the argument, as if it were the
+/// whole buffer, so [`Basic`] can be used as is.
+///
+/// [`Basic`]: struct.Basic.html
+/// [`Command`]: struct.Command.html
+/// [`Flag`]: struct.Flag.html
+/// [`completer`]: #method.completer
+/// [`suggester`]: #method.suggester
+#[derive(Default)]
+pub struct Argument {
+    completer: Option<Box<dyn Completer>>,
+    suggester: Option<Box<dyn Suggester>>,
+    repeated: bool,
+}
+
+impl CommandTree {
+    /// Creates an empty tree.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a top level command.
+    #[must_use]
+    pub fn command(mut self, command: Command) -> Self {
+        self.0.subcommands.push(command);
+        self
+    }
+}
+
+impl Command {
+    /// Creates a command with the given `name` and nothing after it.
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.chars().collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the description shown next to the command when it is suggested.
+    #[must_use]
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.chars().collect());
+        self
+    }
+
+    /// Adds a subcommand, which may only come before any positional argument.
+    #[must_use]
+    pub fn subcommand(mut self, subcommand: Command) -> Self {
+        self.subcommands.push(subcommand);
+        self
+    }
+
+    /// Adds a flag, which may come anywhere after the command.
+    #[must_use]
+    pub fn flag(mut self, flag: Flag) -> Self {
+        self.flags.push(flag);
+        self
+    }
+
+    /// Adds the next positional argument.
+    #[must_use]
+    pub fn argument(mut self, argument: Argument) -> Self {
+        self.arguments.push(argument);
+        self
+    }
+
+    fn find_subcommand(&self, name: &[char]) -> Option<&Command> {
+        self.subcommands
+            .iter()
+            .find(|subcommand| subcommand.name == name)
+    }
+
+    fn find_flag(&self, name: &[char]) -> Option<&Flag> {
+        self.flags
+            .iter()
+            .find(|flag| flag.names.iter().any(|flag_name| flag_name == name))
+    }
+
+    /// The argument at `position`, where the last argument takes every position after it if
+    /// it is repeated.
+    fn argument_at(&self, position: usize) -> Option<&Argument> {
+        self.arguments.get(position).or_else(|| {
+            self.arguments
+                .last()
+                .filter(|argument| argument.repeated && position >= self.arguments.len())
+        })
+    }
+}
+
+impl Flag {
+    /// Creates a flag with the given `name`, e.g. `--verbose`.
+    ///
+    /// The name should start with `-`, since only words starting with `-` are completed as
+    /// flags.
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        Self {
+            names: vec![name.chars().collect()],
+            description: None,
+            value: None,
+        }
+    }
+
+    /// Adds another name for the flag, e.g. `-v`.
+    ///
+    /// Aliases are recognized, but only offered when the name does not match what was typed.
+    #[must_use]
+    pub fn alias(mut self, alias: &str) -> Self {
+        self.names.push(alias.chars().collect());
+        self
+    }
+
+    /// Sets the description shown next to the flag when it is suggested.
+    #[must_use]
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.chars().collect());
+        self
+    }
+
+    /// Makes the flag take the word after it as a value, completed as `argument`.
+    #[must_use]
+    pub fn value(mut self, argument: Argument) -> Self {
+        self.value = Some(argument);
+        self
+    }
+}
+
+impl Argument {
+    /// Creates a free text argument.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an argument that is one of `options`.
+    #[must_use]
+    pub fn choices(options: &[&str]) -> Self {
+        Self::new()
+            .completer(Basic::new(options))
+            .suggester(Basic::new(options).filter(true))
+    }
+
+    /// Sets the in-line completion provider for the argument.
+    #[must_use]
+    pub fn completer(mut self, completer: impl Completer + 'static) -> Self {
+        self.completer = Some(Box::new(completer));
+        self
+    }
+
+    /// Sets the drop-down suggestion provider for the argument.
+    #[must_use]
+    pub fn suggester(mut self, suggester: impl Suggester + 'static) -> Self {
+        self.suggester = Some(Box::new(suggester));
+        self
+    }
+
+    /// Lets the argument take every position after its own, e.g. for a list of files.
+    ///
+    /// Only the last argument of a command may be repeated.
+    #[must_use]
+    pub fn repeated(mut self, repeated: bool) -> Self {
+        self.repeated = repeated;
+        self
+    }
+}
+
+impl Completer for CommandTree {
+    fn completion_for(&self, context: &dyn Context) -> Option<Cow<'_, [char]>> {
+        let current = Current::of(context);
+        if current.span.end != context.cursor() {
+            return None;
+        }
+
+        match self.expected(&current) {
+            Expected::Argument(argument) => {
+                let completer = argument.completer.as_ref()?;
+                completer.completion_for(&current.scoped(context))
+            }
+            Expected::Names(names) => {
+                if current.prefix.is_empty() {
+                    return None;
+                }
+
+                let (first, rest) = names.split_first()?;
+                let first = first.0;
+                let common = rest.iter().fold(first.len(), |common, (name, _)| {
+                    first
+                        .iter()
+                        .zip(name.iter())
+                        .take(common)
+                        .take_while(|(a, b)| a == b)
+                        .count()
+                });
+
+                if common > current.prefix.len() {
+                    Some(Cow::Borrowed(&first[current.prefix.len()..common]))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+impl Suggester for CommandTree {
+    fn candidates_for(&self, context: &dyn Context) -> Vec<Candidate<'_>> {
+        let current = Current::of(context);
+
+        match self.expected(&current) {
+            Expected::Argument(argument) => {
+                argument
+                    .suggester
+                    .as_ref()
+                    .map_or_else(Vec::new, |suggester| {
+                        suggester
+                            .candidates_for(&current.scoped(context))
+                            .into_iter()
+                            .map(|candidate| {
+                                let span = candidate.span();
+                                Candidate {
+                                    span: span.start + current.span.start
+                                        ..span.end + current.span.start,
+                                    ..candidate
+                                }
+                            })
+                            .collect()
+                    })
+            }
+            Expected::Names(names) => {
+                // Names end the word, unless there is already whitespace after it
+                let buffer = context.buffer();
+                let separated = buffer
+                    .get(current.span.end)
+                    .is_some_and(|c| c.is_whitespace());
+
+                names
+                    .into_iter()
+                    .map(|(name, description)| {
+                        let mut text = name.to_vec();
+                        if !separated {
+                            text.push(' ');
+                        }
+
+                        let candidate = Candidate::new(text, current.span.clone()).with_label(name);
+                        match description {
+                            Some(description) => candidate.with_description(description),
+                            None => candidate,
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// What may be typed at the word under the cursor.
+enum Expected<'a> {
+    /// The names of the subcommands or flags that start with what was typed, along with their
+    /// descriptions
+    Names(Vec<(&'a [char], Option<&'a [char]>)>),
+    /// The value of a positional argument or a flag
+    Argument(&'a Argument),
+}
+
+impl CommandTree {
+    /// Walks the words before the `current` one down the tree, to find what may be typed next.
+    fn expected(&self, current: &Current) -> Expected<'_> {
+        let mut command = &self.0;
+        let mut position = 0;
+        let mut given: Vec<&Flag> = Vec::new();
+        let mut value = None;
+
+        for word in &current.previous {
+            if value.take().is_some() {
+                continue;
+            }
+
+            if position == 0 {
+                if let Some(subcommand) = command.find_subcommand(word) {
+                    command = subcommand;
+                    given.clear();
+                    continue;
+                }
+            }
+
+            if word.first() == Some(&'-') {
+                if let Some(flag) = command.find_flag(word) {
+                    given.push(flag);
+                    value = flag.value.as_ref();
+                }
+                continue;
+            }
+
+            position += 1;
+        }
+
+        if let Some(argument) = value {
+            return Expected::Argument(argument);
+        }
+
+        let prefix = &current.prefix[..];
+        if prefix.first() == Some(&'-') {
+            return Expected::Names(
+                command
+                    .flags
+                    .iter()
+                    .filter(|flag| !given.iter().any(|given| std::ptr::eq(*given, *flag)))
+                    .filter_map(|flag| {
+                        flag.names
+                            .iter()
+                            .find(|name| name.starts_with(prefix))
+                            .map(|name| (name.as_slice(), flag.description.as_deref()))
+                    })
+                    .collect(),
+            );
+        }
+
+        // Subcommands take precedence over an argument in the same position
+        let names = if position == 0 {
+            command
+                .subcommands
+                .iter()
+                .filter(|subcommand| subcommand.name.starts_with(prefix))
+                .map(|subcommand| {
+                    (
+                        subcommand.name.as_slice(),
+                        subcommand.description.as_deref(),
+                    )
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        match command.argument_at(position) {
+            Some(argument) if names.is_empty() => Expected::Argument(argument),
+            _ => Expected::Names(names),
+        }
+    }
+}
+
+/// The word under the cursor, and the words before it.
+struct Current {
+    /// The words before the current one, unescaped
+    previous: Vec<Vec<char>>,
+    /// The span of the current word in the buffer, which may be empty
+    span: std::ops::Range<usize>,
+    /// The part of the current word before the cursor, unescaped
+    prefix: Vec<char>,
+}
+
+impl Current {
+    fn of(context: &dyn Context) -> Self {
+        Self {
+            previous: context
+                .tokens()
+                .into_iter()
+                .take(context.argument_index())
+                .map(|token| token.text().to_vec())
+                .collect(),
+            span: context.word_start()..context.word_end(),
+            prefix: context.current_word(),
+        }
+    }
+
+    /// A context with only the current word as the buffer.
+    fn scoped<'a>(&self, context: &'a dyn Context) -> Scoped<'a> {
+        Scoped {
+            buffer: &context.buffer()[self.span.clone()],
+            cursor: context.cursor() - self.span.start,
+        }
+    }
+}
+
+/// A [`Context`](../prompt/context/trait.Context.html) limited to a single word.
+struct Scoped<'a> {
+    buffer: &'a [char],
+    cursor: usize,
+}
+
+impl Context for Scoped<'_> {
+    fn buffer(&self) -> &[char] {
+        self.buffer
+    }
+
+    fn cursor(&self) -> usize {
+        self.cursor
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Argument, Command, CommandTree, Flag};
+    use crate::completion::{Basic, Candidate, Completer, Suggester};
+    use crate::test::mock::Context as Mock;
+
+    fn tree() -> CommandTree {
+        CommandTree::new()
+            .command(
+                Command::new("db")
+                    .subcommand(
+                        Command::new("connect").argument(Argument::choices(&["local", "remote"])),
+                    )
+                    .subcommand(
+                        Command::new("list")
+                            .flag(Flag::new("--verbose").alias("-v"))
+                            .flag(
+                                Flag::new("--output").value(Argument::choices(&["json", "table"])),
+                            ),
+                    ),
+            )
+            .command(
+                Command::new("open").argument(
+                    Argument::new()
+                        .suggester(Basic::new(&["one", "two"]))
+                        .repeated(true),
+                ),
+            )
+            .command(Command::new("quit"))
+    }
+
+    fn complete(buffer: &str) -> Option<String> {
+        tree()
+            .completion_for(&Mock::from(buffer))
+            .map(|completion| completion.iter().collect())
+    }
+
+    fn suggest(buffer: &str) -> Vec<String> {
+        let context = Mock::from(buffer);
+        tree()
+            .candidates_for(&context)
+            .iter()
+            .map(|candidate| candidate.apply(&context.buffer).0.iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn suggest_commands() {
+        assert_eq!(suggest(""), vec!["db ", "open ", "quit "]);
+        assert_eq!(suggest("q"), vec!["quit "]);
+        assert_eq!(suggest("db "), vec!["db connect ", "db list "]);
+        assert_eq!(suggest("db l"), vec!["db list "]);
+        assert!(suggest("quit ").is_empty());
+    }
+
+    #[test]
+    fn suggest_flags() {
+        assert_eq!(
+            suggest("db list -"),
+            vec!["db list --verbose ", "db list --output "]
+        );
+        assert_eq!(suggest("db list -v"), vec!["db list -v "]);
+        assert_eq!(
+            suggest("db list --verbose -"),
+            vec!["db list --verbose --output "]
+        );
+        assert_eq!(
+            suggest("db list --output "),
+            vec!["db list --output json", "db list --output table"]
+        );
+        assert_eq!(
+            suggest("db list --output json -"),
+            vec!["db list --output json --verbose "]
+        );
+    }
+
+    #[test]
+    fn describe_names() {
+        let tree = CommandTree::new()
+            .command(Command::new("connect").description("open a session"))
+            .command(Command::new("close"));
+        let context = Mock {
+            buffer: "c --now".chars().collect(),
+            cursor: 1,
+        };
+        let candidates = tree.candidates_for(&context);
+
+        let describe = |candidate: &Candidate<'_>| {
+            (
+                candidate.label().iter().collect::<String>(),
+                candidate.text().iter().collect::<String>(),
+                candidate
+                    .description()
+                    .map(|d| d.iter().collect::<String>()),
+            )
+        };
+        assert_eq!(
+            candidates.iter().map(describe).collect::<Vec<_>>(),
+            vec![
+                (
+                    String::from("connect"),
+                    String::from("connect"),
+                    Some(String::from("open a session"))
+                ),
+                (String::from("close"), String::from("close"), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn suggest_arguments() {
+        assert_eq!(suggest("db connect r"), vec!["db connect remote"]);
+        assert!(suggest("db connect remote ").is_empty());
+        assert_eq!(suggest("open one t"), vec!["open one one", "open one two"]);
+    }
+
+    #[test]
+    fn complete_names_and_arguments() {
+        assert_eq!(complete("q"), Some(String::from("uit")));
+        assert_eq!(complete("db co"), Some(String::from("nnect")));
+        assert_eq!(complete("db list --o"), Some(String::from("utput")));
+        assert_eq!(complete("db list --output t"), Some(String::from("able")));
+        assert_eq!(complete("db connect l"), Some(String::from("ocal")));
+        assert_eq!(complete("db "), None);
+        assert_eq!(complete("x"), None);
+    }
+
+    #[test]
+    fn replace_only_the_current_word() {
+        let context = Mock {
+            buffer: "db connect r --all".chars().collect(),
+            cursor: 12,
+        };
+        let tree = tree();
+        let candidates = tree.candidates_for(&context);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].span(), 11..12);
+    }
+}
@@ -0,0 +1,291 @@
+use super::{Candidate, Context, Suggester};
+
+const SCORE_MATCH: i32 = 16;
+const PENALTY_GAP_START: i32 = 3;
+const PENALTY_GAP_EXTENSION: i32 = 1;
+const BONUS_BOUNDARY: i32 = 8;
+const BONUS_CAMEL_CASE: i32 = 7;
+const BONUS_CONSECUTIVE: i32 = 4;
+const FIRST_CHAR_MULTIPLIER: i32 = 2;
+
+/// A suggester that ranks its options by how well they fuzzy match the word under the cursor,
+/// in the spirit of [`fzf`].
+///
+/// An option matches if the characters of the word appear in it in order, although not
+/// necessarily next to each other. Matches score higher when the characters are consecutive,
+/// or start a word or a `camelCase` hump of the option, and lower for each character skipped
+/// in between. The matching is case-insensitive, unless the word contains an upper case
+/// character.
+///
+/// The matching options replace the word under the cursor, as split by [`tokenize`], best
+/// first. Ties go to the shorter option, and then to the one given first.
+///
+/// # Example
+///
+/// ```no_run
+/// use rucline::completion::Fuzzy;
+/// use rucline::Prompt;
+///
+/// let prompt = Prompt::from("$ ").suggester(Fuzzy::new(&["checkout", "cherry-pick", "commit"]));
+/// ```
+///
+/// The ranking, along with the positions of the matched characters, is also available through
+/// [`rank`], e.g. to highlight them:
+///
+/// ```
+/// use rucline::completion::Fuzzy;
+///
+/// let fuzzy = Fuzzy::new(&["checkout", "cherry-pick", "commit"]);
+/// let ranked = fuzzy.rank(&['c', 'p']);
+///
+/// assert_eq!(ranked.len(), 1);
+/// assert_eq!(ranked[0].positions(), &[0, 7]);
+/// ```
+///
+/// [`fzf`]: https://github.com/junegunn/fzf
+/// [`rank`]: #method.rank
+/// [`tokenize`]: ../context/fn.tokenize.html
+pub struct Fuzzy(Vec<Vec<char>>);
+
+/// An option matched by [`Fuzzy`](struct.Fuzzy.html).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FuzzyMatch<'a> {
+    text: &'a [char],
+    score: i32,
+    positions: Vec<usize>,
+}
+
+impl<'a> FuzzyMatch<'a> {
+    /// The option that matched.
+    #[must_use]
+    pub fn text(&self) -> &'a [char] {
+        self.text
+    }
+
+    /// How well the option matched. Higher is better.
+    #[must_use]
+    pub fn score(&self) -> i32 {
+        self.score
+    }
+
+    /// The indices of the characters of the option that matched, in order.
+    #[must_use]
+    pub fn positions(&self) -> &[usize] {
+        &self.positions
+    }
+}
+
+impl Fuzzy {
+    /// Creates a new instance from the list of `options` given.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - A list of `&str` to serve as options for suggestions.
+    #[must_use]
+    pub fn new(options: &[&str]) -> Self {
+        Self(
+            options
+                .iter()
+                .map(|string| string.chars().collect())
+                .collect(),
+        )
+    }
+
+    /// Returns the options that match `pattern`, best first.
+    ///
+    /// An empty `pattern` matches every option, in the order they were given.
+    #[must_use]
+    pub fn rank(&self, pattern: &[char]) -> Vec<FuzzyMatch<'_>> {
+        let mut matches = self
+            .0
+            .iter()
+            .filter_map(|option| {
+                fuzzy_match(pattern, option).map(|(score, positions)| FuzzyMatch {
+                    text: option,
+                    score,
+                    positions,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // The sort is stable, so ties keep the order the options were given in
+        matches.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| a.text.len().cmp(&b.text.len()))
+        });
+        matches
+    }
+}
+
+impl Suggester for Fuzzy {
+    fn candidates_for(&self, context: &dyn Context) -> Vec<Candidate<'_>> {
+        let span = context.word_start()..context.word_end();
+        self.rank(&context.current_word())
+            .into_iter()
+            .map(|matched| {
+                Candidate::new(matched.text, span.clone()).with_highlights(matched.positions)
+            })
+            .collect()
+    }
+}
+
+/// The bonus for matching the character at `index`, depending on what precedes it.
+fn bonus(candidate: &[char], index: usize) -> i32 {
+    let current = candidate[index];
+    match index.checked_sub(1).map(|previous| candidate[previous]) {
+        None => BONUS_BOUNDARY,
+        Some(previous) if !previous.is_alphanumeric() => BONUS_BOUNDARY,
+        Some(previous) if previous.is_lowercase() && current.is_uppercase() => BONUS_CAMEL_CASE,
+        Some(previous) if !previous.is_numeric() && current.is_numeric() => BONUS_CAMEL_CASE,
+        Some(_) => 0,
+    }
+}
+
+/// Scores the best way of matching `pattern` as a subsequence of `candidate`, returning the
+/// score and the matched positions, or `None` if it does not match.
+// Allowed because the gap is bounded by the length of the candidate
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn fuzzy_match(pattern: &[char], candidate: &[char]) -> Option<(i32, Vec<usize>)> {
+    if pattern.is_empty() {
+        return Some((0, Vec::new()));
+    }
+
+    let case_sensitive = pattern.iter().any(|c| c.is_uppercase());
+    let matches = |p: char, c: char| {
+        if case_sensitive {
+            p == c
+        } else {
+            p.to_lowercase().eq(c.to_lowercase())
+        }
+    };
+
+    // `scores[i][j]` is the best score with the `i`th pattern character matched at `j`,
+    // and `previous[i][j]` where the character before it was matched
+    let mut scores = vec![vec![None; candidate.len()]; pattern.len()];
+    let mut previous = vec![vec![0; candidate.len()]; pattern.len()];
+
+    for (i, p) in pattern.iter().enumerate() {
+        for (j, c) in candidate.iter().enumerate() {
+            if !matches(*p, *c) {
+                continue;
+            }
+
+            let bonus = bonus(candidate, j);
+            if i == 0 {
+                scores[i][j] = Some(SCORE_MATCH + bonus * FIRST_CHAR_MULTIPLIER);
+                continue;
+            }
+
+            let best = (0..j)
+                .filter_map(|k| {
+                    let score = scores[i - 1][k]?;
+                    let gap = (j - k - 1) as i32;
+                    let score = if gap == 0 {
+                        score + bonus.max(BONUS_CONSECUTIVE)
+                    } else {
+                        score + bonus - PENALTY_GAP_START - PENALTY_GAP_EXTENSION * (gap - 1)
+                    };
+                    Some((score, k))
+                })
+                .max_by_key(|(score, _)| *score);
+
+            if let Some((score, k)) = best {
+                scores[i][j] = Some(score + SCORE_MATCH);
+                previous[i][j] = k;
+            }
+        }
+    }
+
+    let last = pattern.len() - 1;
+    let (mut position, score) = scores[last]
+        .iter()
+        .enumerate()
+        .filter_map(|(j, score)| score.map(|score| (j, score)))
+        .max_by_key(|(_, score)| *score)?;
+
+    let mut positions = vec![position];
+    for i in (1..=last).rev() {
+        position = previous[i][position];
+        positions.push(position);
+    }
+    positions.reverse();
+
+    Some((score, positions))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{fuzzy_match, Fuzzy};
+    use crate::completion::Suggester;
+    use crate::test::mock::Context as Mock;
+
+    fn chars(string: &str) -> Vec<char> {
+        string.chars().collect()
+    }
+
+    fn positions(pattern: &str, candidate: &str) -> Option<Vec<usize>> {
+        fuzzy_match(&chars(pattern), &chars(candidate)).map(|(_, positions)| positions)
+    }
+
+    fn score(pattern: &str, candidate: &str) -> i32 {
+        fuzzy_match(&chars(pattern), &chars(candidate)).unwrap().0
+    }
+
+    #[test]
+    fn match_subsequences() {
+        assert_eq!(positions("abc", "a_b_c"), Some(vec![0, 2, 4]));
+        assert_eq!(positions("acb", "a_b_c"), None);
+        assert_eq!(positions("", "abc"), Some(vec![]));
+        assert_eq!(positions("a", ""), None);
+    }
+
+    #[test]
+    fn prefer_boundaries_and_consecutive_characters() {
+        // Word starts are preferred over the first occurrence
+        assert_eq!(positions("fb", "foo-bar"), Some(vec![0, 4]));
+        assert_eq!(positions("b", "abc-bcd"), Some(vec![4]));
+        assert_eq!(positions("mc", "myCommand"), Some(vec![0, 2]));
+
+        assert!(score("abc", "abcxx") > score("abc", "axbxc"));
+        assert!(score("ab", "a-b") > score("ab", "axxb"));
+    }
+
+    #[test]
+    fn use_smart_case() {
+        assert!(positions("abc", "ABC").is_some());
+        assert!(positions("Abc", "abc").is_none());
+        assert!(positions("Abc", "Abc").is_some());
+    }
+
+    #[test]
+    fn rank_best_first() {
+        let fuzzy = Fuzzy::new(&["axbxc", "abc", "zzz", "abcd"]);
+        let ranked = fuzzy
+            .rank(&chars("abc"))
+            .iter()
+            .map(|matched| matched.text().iter().collect::<String>())
+            .collect::<Vec<_>>();
+
+        assert_eq!(ranked, vec!["abc", "abcd", "axbxc"]);
+        assert_eq!(fuzzy.rank(&[]).len(), 4);
+    }
+
+    #[test]
+    fn replace_current_word() {
+        let fuzzy = Fuzzy::new(&["checkout", "cherry-pick", "commit"]);
+        let candidates = fuzzy.candidates_for(&Mock::from("git cp"));
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].text(), &chars("cherry-pick")[..]);
+        assert_eq!(candidates[0].span(), 4..6);
+    }
+
+    #[test]
+    fn highlight_matched_characters() {
+        let fuzzy = Fuzzy::new(&["checkout", "cherry-pick", "commit"]);
+        let candidates = fuzzy.candidates_for(&Mock::from("git cp"));
+
+        assert_eq!(candidates[0].highlights(), &[0, 7]);
+    }
+}
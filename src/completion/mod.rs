@@ -0,0 +1,1067 @@
+// TODO: Consider impact of using &str instead of &[char] in public API for completions
+
+//! Provides completion methods for [`Prompt`] when reading lines.
+//!
+//! By default, no completions are performed upon user interaction. However, if a [`Completer`]
+//! or a [`Suggester`] are provided, the [`Prompt`] will query for completions for the current
+//! state of the line.
+//!
+//! Notably, the traits from this module expose a `&[char]` interface. This is addressed in more detail
+//! below.
+//!
+//! This module also includes a convenience wrapper for lambdas, allowing quick implementation
+//! of completions with closures.
+//!
+//! # Examples
+//!
+//! Basic implementation for in-line completion:
+//!
+//! ```no_run
+//! use rucline::completion::{Completer, Context};
+//!
+//! struct Basic(Vec<Vec<char>>);
+//! impl Completer for Basic {
+//!   fn complete_for(&self, context: &dyn Context) -> Option<&[char]> {
+//!       let buffer = context.buffer();
+//!       if buffer.is_empty() {
+//!           None
+//!       } else {
+//!           self.0
+//!               .iter()
+//!               .find(|completion| completion.starts_with(buffer))
+//!               .map(|completion| &completion[buffer.len()..])
+//!       }
+//!   }
+//! }
+//! ```
+//!
+//! Basic implementation for drop-down suggestions:
+//!
+//! ```no_run
+//! use rucline::completion::{Context, Suggester};
+//!
+//! struct Basic(Vec<Vec<char>>);
+//! impl Suggester for Basic {
+//!   fn suggest_for(&self, context: &dyn Context) -> Vec<&[char]> {
+//!       self.0.iter().map(Vec::as_slice).collect::<Vec<_>>()
+//!   }
+//! }
+//! ```
+//!
+//! Basic implementation for drop-down suggestions with lambda:
+//!
+//! ```no_run
+//! use rucline::completion::{Context, Lambda};
+//!
+//! let completions: Vec<Vec<char>> = ["abc", "def", "example word", "weird \"˚∆˙\""]
+//!    .iter()
+//!    .map(|s| s.chars().collect())
+//!    .collect();
+//!let completer = Lambda::from(|c: &dyn Context| {
+//!    completions
+//!        .iter()
+//!        .filter(|s| s.starts_with(c.buffer()))
+//!        .map(Vec::as_slice)
+//!        .collect::<Vec<_>>()
+//!});
+//! ```
+//!
+//! # Usage of `&[char]`
+//!
+//! Strings in Rust support UTF-8, that means that a single character that is rendered in the
+//! terminal might be from a single byte to four. To support cursor positioning and editing
+//! of the buffer, the data must be of a constant size, i.e. `char`.
+//!
+//! This is less memory efficient, however more effient in runtime - both in terms of CPU and,
+//! unintuitevily, memory.
+//!
+//! If the implementation stores and returns a `String`, this will "compact" the characters into
+//! the minimum necessary size for representing that glyph. However, once printed in the terminal,
+//! the data must be converted to `char`, as explained before. With that in mind, that would cause
+//! overhead of CPU and memory to convert and store the `String` to a array of `char`.
+//! Furthermore, it would not be possible to have a single memory allocation and pass around
+//! references, since allocation is needed to convert `String` -> `[char]`.
+//!
+//! By storing a `Vec<char>`, the trait implementation is able to return references to data that
+//! will not reallocate and will not need to be converted.
+//!
+//! With that said, if performance is not a concern, the trait implementaion may simply store a
+//! a `String` and return the `chars()` output.
+//!
+//! # Computing completions
+//!
+//! Completions that are not stored anywhere, e.g. computed from the buffer or looked up in a
+//! database, cannot be returned as a reference. For those, the traits also offer
+//! [`completion_for`] and [`suggestions_for`], which return a [`Cow`] that may own its data.
+//!
+//! ```no_run
+//! use rucline::completion::{Completer, Context};
+//! use std::borrow::Cow;
+//!
+//! struct Uppercase;
+//! impl Completer for Uppercase {
+//!     fn completion_for(&self, context: &dyn Context) -> Option<Cow<'_, [char]>> {
+//!         let upper = context.buffer().iter().flat_map(|c| c.to_uppercase());
+//!         Some(Cow::Owned(std::iter::once(' ').chain(upper).collect()))
+//!     }
+//! }
+//! ```
+//!
+//! # Completing words
+//!
+//! Suggestions replace the whole buffer by default. To replace only part of it, e.g. the word
+//! under the cursor, a [`Suggester`] may implement [`candidates_for`] instead, returning each
+//! [`Candidate`] along with the span of the buffer it replaces. Everything outside of the span
+//! is left intact. The [`Context`] finds the word under the cursor, splitting the buffer as a
+//! shell would.
+//!
+//! ```no_run
+//! use rucline::completion::{Candidate, Context, Suggester};
+//!
+//! struct Shout;
+//! impl Suggester for Shout {
+//!     fn candidates_for(&self, context: &dyn Context) -> Vec<Candidate<'_>> {
+//!         // Replace the word under the cursor with what was typed of it, in upper case
+//!         let upper = context
+//!             .current_word()
+//!             .iter()
+//!             .flat_map(|c| c.to_uppercase())
+//!             .collect::<Vec<_>>();
+//!         vec![Candidate::new(upper, context.word_start()..context.word_end())]
+//!     }
+//! }
+//! ```
+//!
+//! # See also
+//! * [`Basic`]
+//! * [`CommandTree`]
+//! * [`Fuzzy`]
+//! * [`Lambda`]
+//! * [`Paths`]
+//!
+//! [`Basic`]: struct.Basic.html
+//! [`Candidate`]: struct.Candidate.html
+//! [`CommandTree`]: struct.CommandTree.html
+//! [`Context`]: ../context/trait.Context.html
+//! [`Fuzzy`]: struct.Fuzzy.html
+//! [`Lambda`]: struct.Lambda.html
+//! [`Paths`]: struct.Paths.html
+//! [`Prompt`]: ../prompt/struct.Prompt.html
+//! [`Completer`]: trait.Completer.html
+//! [`Suggester`]: trait.Suggester.html
+//! [`completion_for`]: trait.Completer.html#method.completion_for
+//! [`suggestions_for`]: trait.Suggester.html#method.suggestions_for
+//! [`candidates_for`]: trait.Suggester.html#method.candidates_for
+//! [`Cow`]: https://doc.rust-lang.org/std/borrow/enum.Cow.html
+
+mod command;
+mod fuzzy;
+mod path;
+
+use std::borrow::Cow;
+
+pub use crate::Context;
+pub use command::{Argument, Command, CommandTree, Flag};
+pub use fuzzy::{Fuzzy, FuzzyMatch};
+pub use path::Paths;
+
+/// Completes the buffer in-line.
+///
+/// Whenever the line is edited, e.g. [`Write`] or [`Delete`], the [`Prompt`] will ask the
+/// `Completer` for a possible completion to **append** to the current buffer. The implementation
+/// may use the [`Context`] to decide which completions are applicable.
+///
+/// The buffer is not actually changed, the completion is only rendered. A [`Complete`] action
+/// must be issued to incorporate the completion into the buffer.
+///
+/// # Example
+///
+/// Basic implementation:
+///
+/// ```no_run
+/// use rucline::completion::{Completer, Context};
+///
+/// struct Basic(Vec<Vec<char>>);
+/// impl Completer for Basic {
+///   fn complete_for(&self, context: &dyn Context) -> Option<&[char]> {
+///       let buffer = context.buffer();
+///       if buffer.is_empty() {
+///           None
+///       } else {
+///           self.0
+///               .iter()
+///               .find(|completion| completion.starts_with(buffer))
+///               .map(|completion| &completion[buffer.len()..])
+///       }
+///   }
+/// }
+/// ```
+///
+/// # See also
+/// * [`Basic`]
+///
+/// [`Basic`]: struct.Basic.html#implementations
+/// [`Complete`]: ../actions/enum.Action.html#variant.Complete
+/// [`Context`]: ../prompt/context/trait.Context.html
+/// [`Delete`]: ../actions/enum.Action.html#variant.Delete
+/// [`Prompt`]: ../prompt/struct.Prompt.html
+/// [`Write`]: ../actions/enum.Action.html#variant.Write
+pub trait Completer {
+    /// Provides the in-line completion for a given [`Context`].
+    ///
+    /// Whenever the line is edited, e.g. [`Write`] or [`Delete`], the [`Prompt`] will call
+    /// `complete_for` for a possible completion to **append** to the current buffer.
+    ///
+    /// # Arguments
+    /// * [`context`] - The current context in which this event is coming in.
+    ///
+    /// # Return
+    /// * [`Option<&[char]>`] - A completion to be rendered. `None` if there are no suggestions.
+    ///
+    /// # See also
+    /// * [`Basic`]
+    ///
+    /// [`Context`]: ../prompt/context/trait.Context.html
+    /// [`Completer`]: trait.Completer.html
+    /// [`Basic`]: struct.Basic.html#implementations
+    fn complete_for(&self, context: &dyn Context) -> Option<&[char]> {
+        let _ = context;
+        None
+    }
+
+    /// Provides the in-line completion for a given [`Context`], which may be computed on the
+    /// spot instead of borrowed from the `Completer`.
+    ///
+    /// This is what the [`Prompt`] calls. By default, it borrows the result of
+    /// [`complete_for`], so only one of the two methods should be implemented.
+    ///
+    /// # Arguments
+    /// * [`context`] - The current context in which this event is coming in.
+    ///
+    /// # Return
+    /// * [`Option<Cow<[char]>>`] - A completion to be rendered. `None` if there are no
+    ///   suggestions.
+    ///
+    /// [`Context`]: ../prompt/context/trait.Context.html
+    /// [`Prompt`]: ../prompt/struct.Prompt.html
+    /// [`complete_for`]: #method.complete_for
+    fn completion_for(&self, context: &dyn Context) -> Option<Cow<'_, [char]>> {
+        self.complete_for(context).map(Cow::Borrowed)
+    }
+}
+
+/// Generates a list of possible values for the [`Prompt`] buffer, usually associated with the
+/// `Tab` key.
+///
+/// Whenever the [`Suggest`] action is triggered,  the [`Prompt`] will ask the
+/// `Suggester` for a list of values to **replace** to the current buffer, or a span of it when
+/// returned as a [`Candidate`].
+/// This list is kept by the [`Prompt`] for cycling back and forth until it is dropped by
+/// either accepting the suggestions or cancelling it. The implementation
+/// may use the [`Context`] to decide which completions are applicable.
+///
+/// The buffer is not actually changed until the suggestion is accepted by either a [`Write`], a
+/// [`Delete`], [`Accept`] or a [`Move`], while a suggestion is selected.
+///
+/// The suggestions are laid out in as many columns as fit the terminal, unless they have
+/// descriptions, and at most ten rows are shown at a time, followed by how many more there are.
+/// While a suggestion is selected, the arrows move around this menu and `PageUp` and `PageDown`
+/// turn its pages, unless an [`Overrider`] claims those keys first.
+///
+/// [`Accept`]: ../actions/enum.Action.html#variant.Accept
+/// [`Candidate`]: struct.Candidate.html
+/// [`Context`]: ../prompt/context/trait.Context.html
+/// [`Delete`]: ../actions/enum.Action.html#variant.Delete
+/// [`Move`]: ../actions/enum.Action.html#variant.Move
+/// [`Overrider`]: ../actions/trait.Overrider.html
+/// [`Prompt`]: ../prompt/struct.Prompt.html
+/// [`Suggest`]: ../actions/enum.Action.html#variant.Suggest
+/// [`Write`]: ../actions/enum.Action.html#variant.Write
+pub trait Suggester {
+    /// Whenever the [`Suggest`] action is triggered,  the [`Prompt`] will ask the
+    /// `Suggester` for a list of values to **replace** to the current buffer.
+    ///
+    /// # Examples
+    ///
+    /// Basic implementation:
+    ///
+    /// ```no_run
+    /// # struct Basic(Vec<Vec<char>>);
+    /// # impl rucline::completion::Suggester for Basic {
+    ///  fn suggest_for(&self, _: &dyn rucline::Context) -> Vec<&[char]> {
+    ///     self.0.iter().map(Vec::as_slice).collect::<Vec<_>>()
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// # Arguments
+    /// * [`context`] - The current context in which this event is coming in.
+    ///
+    /// # Return
+    /// * [`Vec<&[char]>`] - The suggestions to be rendered as drop-down options. Empty if none.
+    ///
+    /// # See also
+    /// * [`Basic`]
+    ///
+    /// [`Basic`]: struct.Basic.html#implementations
+    /// [`Completer`]: trait.Completer.html
+    /// [`Context`]: ../prompt/context/trait.Context.html
+    fn suggest_for(&self, context: &dyn Context) -> Vec<&[char]> {
+        let _ = context;
+        Vec::new()
+    }
+
+    /// Provides the suggestions for a given [`Context`], which may be computed on the spot
+    /// instead of borrowed from the `Suggester`.
+    ///
+    /// By default, it borrows the result of [`suggest_for`], so only one of the two methods
+    /// should be implemented.
+    ///
+    /// # Arguments
+    /// * [`context`] - The current context in which this event is coming in.
+    ///
+    /// # Return
+    /// * [`Vec<Cow<[char]>>`] - The suggestions to be rendered as drop-down options. Empty if
+    ///   none.
+    ///
+    /// [`Context`]: ../prompt/context/trait.Context.html
+    /// [`suggest_for`]: #method.suggest_for
+    fn suggestions_for(&self, context: &dyn Context) -> Vec<Cow<'_, [char]>> {
+        self.suggest_for(context)
+            .into_iter()
+            .map(Cow::Borrowed)
+            .collect()
+    }
+
+    /// Provides the suggestions for a given [`Context`], each replacing only a span of the
+    /// buffer.
+    ///
+    /// This is what the [`Prompt`] calls. By default, each of the [`suggestions_for`] replaces
+    /// the whole buffer.
+    ///
+    /// # Arguments
+    /// * [`context`] - The current context in which this event is coming in.
+    ///
+    /// # Return
+    /// * [`Vec<Candidate>`] - The suggestions to be rendered as drop-down options. Empty if none.
+    ///
+    /// [`Candidate`]: struct.Candidate.html
+    /// [`Context`]: ../prompt/context/trait.Context.html
+    /// [`Prompt`]: ../prompt/struct.Prompt.html
+    /// [`suggestions_for`]: #method.suggestions_for
+    fn candidates_for(&self, context: &dyn Context) -> Vec<Candidate<'_>> {
+        let span = 0..context.buffer().len();
+        self.suggestions_for(context)
+            .into_iter()
+            .map(|text| Candidate::new(text, span.clone()))
+            .collect()
+    }
+}
+
+/// A suggestion that replaces a span of the buffer.
+///
+/// In the drop-down list, the candidate is shown by its label, which is the text that replaces
+/// the span unless set with [`with_label`]. A [`with_description`] is shown dimmed next to it,
+/// aligned with the descriptions of the other candidates.
+///
+/// # Example
+///
+/// ```
+/// use rucline::completion::Candidate;
+///
+/// // Replaces the characters from index 4 up to, but not including, index 6
+/// let candidate = Candidate::new(vec!['c', 'h', 'e', 'c', 'k', 'o', 'u', 't'], 4..6);
+/// assert_eq!(candidate.span(), 4..6);
+///
+/// // Shows `connect` and `open a session`, but inserts `connect `
+/// let candidate = Candidate::new("connect ".chars().collect::<Vec<_>>(), 0..2)
+///     .with_label("connect".chars().collect::<Vec<_>>())
+///     .with_description("open a session".chars().collect::<Vec<_>>());
+/// assert_eq!(candidate.label().len(), 7);
+/// ```
+///
+/// [`with_label`]: #method.with_label
+/// [`with_description`]: #method.with_description
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Candidate<'a> {
+    text: Cow<'a, [char]>,
+    span: std::ops::Range<usize>,
+    label: Option<Cow<'a, [char]>>,
+    description: Option<Cow<'a, [char]>>,
+    highlights: Vec<usize>,
+}
+
+impl<'a> Candidate<'a> {
+    /// Creates a candidate that replaces the characters of the buffer in `span` with `text`.
+    ///
+    /// A span reaching past the end of the buffer is cut short at the end.
+    #[must_use]
+    pub fn new(text: impl Into<Cow<'a, [char]>>, span: std::ops::Range<usize>) -> Self {
+        Self {
+            text: text.into(),
+            span,
+            label: None,
+            description: None,
+            highlights: Vec::new(),
+        }
+    }
+
+    /// Sets the label shown in the drop-down list instead of the text.
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<Cow<'a, [char]>>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Sets the description shown next to the label in the drop-down list.
+    #[must_use]
+    pub fn with_description(mut self, description: impl Into<Cow<'a, [char]>>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the indices of the characters of the label to highlight in the drop-down list, such
+    /// as the characters a search matched.
+    #[must_use]
+    pub fn with_highlights(mut self, highlights: Vec<usize>) -> Self {
+        self.highlights = highlights;
+        self
+    }
+
+    /// The text that replaces the span.
+    #[must_use]
+    pub fn text(&self) -> &[char] {
+        &self.text
+    }
+
+    /// The span of the buffer, in characters, that is replaced.
+    #[must_use]
+    pub fn span(&self) -> std::ops::Range<usize> {
+        self.span.clone()
+    }
+
+    /// The label shown in the drop-down list, which is the text unless set otherwise.
+    #[must_use]
+    pub fn label(&self) -> &[char] {
+        self.label.as_deref().unwrap_or(&self.text)
+    }
+
+    /// The description shown next to the label in the drop-down list, if any.
+    #[must_use]
+    pub fn description(&self) -> Option<&[char]> {
+        self.description.as_deref()
+    }
+
+    /// The indices of the characters of the label highlighted in the drop-down list.
+    #[must_use]
+    pub fn highlights(&self) -> &[usize] {
+        &self.highlights
+    }
+
+    /// The buffer as it would be with this candidate in place, and the position right after
+    /// the inserted text.
+    pub(crate) fn apply(&self, buffer: &[char]) -> (Vec<char>, usize) {
+        let end = self.span.end.min(buffer.len());
+        let start = self.span.start.min(end);
+
+        let mut applied = buffer[..start].to_vec();
+        applied.extend_from_slice(&self.text);
+        let cursor = applied.len();
+        applied.extend_from_slice(&buffer[end..]);
+        (applied, cursor)
+    }
+}
+
+/// A wrapper that converts a lambda into a [`Completer`] or a [`Suggester`].
+///
+/// The valid signatures for the lambdas are:
+/// * [`Completer`] - `Fn(&dyn Context) -> Option<&[char]>`
+/// * [`Suggester`] - `Fn(&dyn Context) -> Vec<&[char]>`
+///
+/// **Note:**
+/// When declaring the lambda, it is necessary to let Rust know of the lifetime of the [`Context`].
+/// So, even if ignoring the argument, the type must be specified for Rust to infer the proper
+/// lifetimes.
+///
+/// # Example
+///
+/// Simple no-op completers:
+///
+/// ```no_run
+/// use rucline::completion::{Context, Lambda};
+/// let inline_completer = Lambda::from(|_: &dyn Context| None);
+/// let dropdown_suggester = Lambda::from(|_: &dyn Context| vec![]);
+/// ```
+///
+/// [`Completer`]: trait.Completer.html
+/// [`Context`]: ../prompt/context/trait.Context.html
+/// [`Suggester`]: trait.Suggester.html
+pub struct Lambda<'a, F, R>
+where
+    F: Fn(&dyn Context) -> R,
+{
+    lambda: F,
+    _phantom: std::marker::PhantomData<&'a ()>,
+}
+
+impl<'a, F> std::convert::From<F> for Lambda<'a, F, Option<&'a [char]>>
+where
+    F: Fn(&dyn Context) -> Option<&'a [char]>,
+{
+    fn from(lambda: F) -> Self {
+        Self {
+            lambda,
+            _phantom: std::marker::PhantomData {},
+        }
+    }
+}
+
+impl<'a, F> std::convert::From<F> for Lambda<'a, F, Vec<&'a [char]>>
+where
+    F: Fn(&dyn Context) -> Vec<&'a [char]>,
+{
+    fn from(lambda: F) -> Self {
+        Self {
+            lambda,
+            _phantom: std::marker::PhantomData {},
+        }
+    }
+}
+
+impl<'a, F> Completer for Lambda<'a, F, Option<&'a [char]>>
+where
+    F: Fn(&dyn Context) -> Option<&'a [char]>,
+{
+    fn complete_for(&self, context: &dyn Context) -> Option<&[char]> {
+        (self.lambda)(context)
+    }
+}
+
+impl<'a, F> Suggester for Lambda<'a, F, Vec<&'a [char]>>
+where
+    F: Fn(&dyn Context) -> Vec<&'a [char]>,
+{
+    fn suggest_for(&self, context: &dyn Context) -> Vec<&[char]> {
+        (self.lambda)(context)
+    }
+}
+
+/// A basic implementation of a completion provider serving both as an example and as a useful
+/// simple completer and suggester.
+///
+/// The default behavior for the traits are:
+/// * [`Completer`] - Return all the matches that start with the current [`Context`]
+///   buffer for in-line completions.
+/// * [`Suggester`] - Return all the entries.
+///
+/// Both can be tuned with [`filter`], [`ignore_case`], [`normalize`] and [`order`], and scoped
+/// to the word under the cursor with [`word_scoped`]. When matching loosely, the completion is
+/// still taken from the option itself, so only the remainder after what was typed is appended.
+///
+/// # Example
+///
+/// ```
+/// use rucline::completion::{Basic, Order, Suggester};
+/// # use rucline::Context;
+/// # struct Typed(Vec<char>);
+/// # impl Context for Typed {
+/// #     fn buffer(&self) -> &[char] { &self.0 }
+/// #     fn cursor(&self) -> usize { self.0.len() }
+/// # }
+///
+/// let basic = Basic::new(&["Commit", "cherry-pick", "checkout"])
+///     .filter(true)
+///     .ignore_case(true)
+///     .order(Order::ShortestFirst);
+///
+/// let suggestions = basic.suggestions_for(&Typed(vec!['c', 'h']));
+/// assert_eq!(suggestions.len(), 2);
+/// assert_eq!(&suggestions[0][..], &['c', 'h', 'e', 'c', 'k', 'o', 'u', 't'][..]);
+/// ```
+///
+/// [`Completer`]: trait.Completer.html
+/// [`Context`]: ../prompt/context/trait.Context.html
+/// [`Suggester`]: trait.Suggester.html
+/// [`filter`]: #method.filter
+/// [`ignore_case`]: #method.ignore_case
+/// [`normalize`]: #method.normalize
+/// [`order`]: #method.order
+/// [`word_scoped`]: #method.word_scoped
+// Allowed because each flag is an independent builder option
+#[allow(clippy::struct_excessive_bools)]
+pub struct Basic {
+    options: Vec<Vec<char>>,
+    filter: bool,
+    ignore_case: bool,
+    normalize: bool,
+    order: Order,
+    word_scoped: bool,
+}
+
+/// The order in which [`Basic`] offers its options.
+///
+/// [`Basic`]: struct.Basic.html
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Order {
+    /// The order in which the options were given
+    #[default]
+    Insertion,
+    /// Alphabetical order, honoring [`ignore_case`] and [`normalize`]
+    ///
+    /// [`ignore_case`]: struct.Basic.html#method.ignore_case
+    /// [`normalize`]: struct.Basic.html#method.normalize
+    Alphabetical,
+    /// The shortest options first, with ties in the order they were given
+    ShortestFirst,
+}
+
+impl Basic {
+    /// Creates a new instance from the list of `options` given.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rucline::completion::Basic;
+    ///
+    /// let basic = Basic::new(&["some programmer was here", "some developer was there"]);
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - A list of `&str` to serve as options for completion and suggestions.
+    #[must_use]
+    pub fn new(options: &[&str]) -> Self {
+        Self {
+            options: options
+                .iter()
+                .map(|string| string.chars().collect())
+                .collect(),
+            filter: false,
+            ignore_case: false,
+            normalize: false,
+            order: Order::Insertion,
+            word_scoped: false,
+        }
+    }
+
+    /// Only suggest the options that start with the current buffer.
+    ///
+    /// An empty buffer still gets every option.
+    #[must_use]
+    pub fn filter(mut self, filter: bool) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Match the buffer against the options regardless of case.
+    #[must_use]
+    pub fn ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    /// Match the buffer against the options after applying Unicode compatibility
+    /// decomposition (NFKD) to both.
+    ///
+    /// This makes, for instance, a precomposed `é` match an `e` followed by a combining acute
+    /// accent, and the `ﬁ` ligature match `fi`.
+    #[must_use]
+    pub fn normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Sets the order in which options are suggested.
+    ///
+    /// When several options match, the completion comes from the first one in this order.
+    #[must_use]
+    pub fn order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Match and replace only the word under the cursor, instead of the whole buffer.
+    ///
+    /// Words are split as described in [`tokenize`]. The part of the word before the cursor is
+    /// matched against the options, and in-line completions are only offered at the end of a
+    /// word.
+    ///
+    /// [`tokenize`]: ../context/fn.tokenize.html
+    #[must_use]
+    pub fn word_scoped(mut self, word_scoped: bool) -> Self {
+        self.word_scoped = word_scoped;
+        self
+    }
+
+    /// The span of the buffer to be replaced, and the part of it to match.
+    fn scope<'a>(&self, context: &'a dyn Context) -> (std::ops::Range<usize>, Cow<'a, [char]>) {
+        let buffer = context.buffer();
+        if self.word_scoped {
+            (
+                context.word_start()..context.word_end(),
+                Cow::Owned(context.current_word()),
+            )
+        } else {
+            (0..buffer.len(), Cow::Borrowed(buffer))
+        }
+    }
+
+    /// The options, in the configured order.
+    fn ordered(&self) -> Vec<&[char]> {
+        let mut options = self.options.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        match self.order {
+            Order::Insertion => {}
+            Order::Alphabetical => {
+                options.sort_by_cached_key(|option| (self.key(option).0, option.to_vec()));
+            }
+            Order::ShortestFirst => options.sort_by_key(|option| option.len()),
+        }
+        options
+    }
+
+    /// Folds `chars` into the form used for matching, along with the length of the folded
+    /// text before each original character, and after the last one.
+    fn key(&self, chars: &[char]) -> (Vec<char>, Vec<usize>) {
+        use unicode_normalization::UnicodeNormalization;
+
+        let mut key = Vec::with_capacity(chars.len());
+        let mut boundaries = Vec::with_capacity(chars.len() + 1);
+
+        for c in chars {
+            boundaries.push(key.len());
+
+            let decomposed = std::iter::once(*c);
+            let decomposed: Box<dyn Iterator<Item = char>> = if self.normalize {
+                Box::new(decomposed.nfkd())
+            } else {
+                Box::new(decomposed)
+            };
+
+            if self.ignore_case {
+                key.extend(decomposed.flat_map(char::to_lowercase));
+            } else {
+                key.extend(decomposed);
+            }
+        }
+        boundaries.push(key.len());
+
+        (key, boundaries)
+    }
+
+    /// Returns the part of `option` after the typed `prefix`, if `option` starts with it.
+    fn remainder<'a>(&self, option: &'a [char], prefix: &[char]) -> Option<&'a [char]> {
+        if !self.ignore_case && !self.normalize {
+            return option
+                .get(prefix.len()..)
+                .filter(|_| option.starts_with(prefix));
+        }
+
+        let (prefix, _) = self.key(prefix);
+        let (key, boundaries) = self.key(option);
+        if !key.starts_with(&prefix) {
+            return None;
+        }
+
+        // The prefix may end halfway through the decomposition of a character, in which case
+        // there is no remainder of the option to offer
+        boundaries
+            .iter()
+            .position(|boundary| *boundary == prefix.len())
+            .map(|index| &option[index..])
+    }
+}
+
+impl Completer for Basic {
+    fn complete_for(&self, context: &dyn Context) -> Option<&[char]> {
+        let (span, prefix) = self.scope(context);
+        if prefix.is_empty() || (self.word_scoped && span.end != context.cursor()) {
+            None
+        } else {
+            // An option that was typed in full leaves nothing to complete
+            self.ordered()
+                .into_iter()
+                .find_map(|option| self.remainder(option, &prefix))
+                .filter(|remainder| !remainder.is_empty())
+        }
+    }
+}
+
+impl Suggester for Basic {
+    fn suggest_for(&self, context: &dyn Context) -> Vec<&[char]> {
+        let (_, prefix) = self.scope(context);
+        self.ordered()
+            .into_iter()
+            .filter(|option| !self.filter || self.remainder(option, &prefix).is_some())
+            .collect()
+    }
+
+    fn candidates_for(&self, context: &dyn Context) -> Vec<Candidate<'_>> {
+        let (span, _) = self.scope(context);
+        self.suggest_for(context)
+            .into_iter()
+            .map(|option| Candidate::new(option, span.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    mod basic {
+        use super::super::{Basic, Completer, Order, Suggester};
+        use crate::test::mock::Context as Mock;
+
+        #[test]
+        fn should_not_complete_if_empty() {
+            let basic = Basic::new(&["some programmer was here", "some developer was there"]);
+            assert_eq!(basic.complete_for(&Mock::empty()), None);
+        }
+
+        #[test]
+        fn should_not_complete_if_context_is_different() {
+            let basic = Basic::new(&["some programmer was here", "some developer was there"]);
+            assert_eq!(basic.complete_for(&Mock::from("a")), None);
+        }
+
+        #[test]
+        fn complete_the_first_match() {
+            let basic = Basic::new(&["zz", "b3", "b2"]);
+            let expected = ['3'];
+            assert_eq!(basic.complete_for(&Mock::from("b")), Some(&expected[..]));
+        }
+
+        #[test]
+        fn only_complete_the_remainder() {
+            let basic = Basic::new(&["abcd", "abc"]);
+            let expected = ['d'];
+            assert_eq!(basic.complete_for(&Mock::from("abc")), Some(&expected[..]));
+        }
+
+        #[test]
+        fn always_suggest() {
+            let basic = Basic::new(&["a", "b", "c"]);
+            let options = [['a'], ['b'], ['c']];
+            let expected = vec![&options[0][..], &options[1][..], &options[2][..]];
+            assert_eq!(&basic.suggest_for(&Mock::empty()), &expected);
+            assert_eq!(&basic.suggest_for(&Mock::from("a")), &expected);
+            assert_eq!(&basic.suggest_for(&Mock::from("z")), &expected);
+        }
+
+        fn suggestions(basic: &Basic, buffer: &str) -> Vec<String> {
+            basic
+                .suggest_for(&Mock::from(buffer))
+                .iter()
+                .map(|suggestion| suggestion.iter().collect())
+                .collect()
+        }
+
+        #[test]
+        fn filter_suggestions() {
+            let basic = Basic::new(&["ab", "b", "abc"]).filter(true);
+            assert_eq!(suggestions(&basic, "a"), vec!["ab", "abc"]);
+            assert_eq!(suggestions(&basic, ""), vec!["ab", "b", "abc"]);
+            assert!(suggestions(&basic, "z").is_empty());
+        }
+
+        #[test]
+        fn ignore_case() {
+            let basic = Basic::new(&["Hello"]).ignore_case(true).filter(true);
+            let expected = ['l', 'o'];
+            assert_eq!(basic.complete_for(&Mock::from("hEl")), Some(&expected[..]));
+            assert_eq!(suggestions(&basic, "HE"), vec!["Hello"]);
+
+            let basic = Basic::new(&["Hello"]);
+            assert_eq!(basic.complete_for(&Mock::from("hel")), None);
+        }
+
+        #[test]
+        fn normalize() {
+            let basic = Basic::new(&["caf\u{e9} au lait", "\u{fb01}le"]).normalize(true);
+            let expected = [' ', 'a', 'u', ' ', 'l', 'a', 'i', 't'];
+            assert_eq!(
+                basic.complete_for(&Mock::from("cafe\u{301}")),
+                Some(&expected[..])
+            );
+            let expected = ['l', 'e'];
+            assert_eq!(basic.complete_for(&Mock::from("fi")), Some(&expected[..]));
+
+            // Halfway through a decomposed character
+            assert_eq!(basic.complete_for(&Mock::from("cafe")), None);
+
+            let basic = Basic::new(&["caf\u{e9}"]);
+            assert_eq!(basic.complete_for(&Mock::from("cafe\u{301}")), None);
+        }
+
+        #[test]
+        fn order() {
+            let options = ["charlie", "Bravo", "al", "alpha"];
+
+            let basic = Basic::new(&options).order(Order::Alphabetical);
+            assert_eq!(
+                suggestions(&basic, ""),
+                vec!["Bravo", "al", "alpha", "charlie"]
+            );
+
+            let basic = basic.ignore_case(true);
+            assert_eq!(
+                suggestions(&basic, ""),
+                vec!["al", "alpha", "Bravo", "charlie"]
+            );
+
+            let basic = Basic::new(&options).order(Order::ShortestFirst);
+            assert_eq!(
+                suggestions(&basic, ""),
+                vec!["al", "Bravo", "alpha", "charlie"]
+            );
+
+            // The completion comes from the first match in order
+            let basic = Basic::new(&["alpha", "al"]);
+            let expected = ['p', 'h', 'a'];
+            assert_eq!(basic.complete_for(&Mock::from("al")), Some(&expected[..]));
+            let basic = basic.order(Order::ShortestFirst);
+            assert_eq!(basic.complete_for(&Mock::from("al")), None);
+        }
+
+        #[test]
+        fn complete_word_under_cursor() {
+            let basic = Basic::new(&["checkout", "commit"]).word_scoped(true);
+            let expected = ['e', 'c', 'k', 'o', 'u', 't'];
+            assert_eq!(
+                basic.complete_for(&Mock::from("git ch")),
+                Some(&expected[..])
+            );
+            assert_eq!(basic.complete_for(&Mock::from("git ")), None);
+
+            // Only at the end of a word
+            let context = Mock {
+                buffer: "git ch --all".chars().collect(),
+                cursor: 6,
+            };
+            assert_eq!(basic.complete_for(&context), Some(&expected[..]));
+            let context = Mock {
+                buffer: "git chx".chars().collect(),
+                cursor: 6,
+            };
+            assert_eq!(basic.complete_for(&context), None);
+        }
+
+        #[test]
+        fn suggest_for_word_under_cursor() {
+            let basic = Basic::new(&["checkout", "commit"])
+                .word_scoped(true)
+                .filter(true);
+            let context = Mock {
+                buffer: "git cox --all".chars().collect(),
+                cursor: 6,
+            };
+            let candidates = basic.candidates_for(&context);
+            assert_eq!(candidates.len(), 1);
+            assert_eq!(candidates[0].text(), &['c', 'o', 'm', 'm', 'i', 't'][..]);
+            assert_eq!(candidates[0].span(), 4..7);
+        }
+    }
+
+    mod candidate {
+        use super::super::{Basic, Candidate, Suggester};
+        use crate::test::mock::Context as Mock;
+
+        #[test]
+        fn apply_to_span() {
+            let buffer = "git cp --all".chars().collect::<Vec<_>>();
+            let candidate = Candidate::new(vec!['c', 'o'], 4..6);
+            let (applied, cursor) = candidate.apply(&buffer);
+            assert_eq!(applied.iter().collect::<String>(), "git co --all");
+            assert_eq!(cursor, 6);
+
+            // Spans past the end are cut short
+            let candidate = Candidate::new(vec!['x'], 10..20);
+            let (applied, cursor) = candidate.apply(&buffer);
+            assert_eq!(applied.iter().collect::<String>(), "git cp --ax");
+            assert_eq!(cursor, 11);
+        }
+
+        #[test]
+        fn replace_whole_buffer_by_default() {
+            let basic = Basic::new(&["abc"]);
+            let candidates = basic.candidates_for(&Mock::from("a b"));
+            assert_eq!(candidates, vec![Candidate::new(vec!['a', 'b', 'c'], 0..3)]);
+        }
+    }
+
+    mod lambda {
+        use super::super::{Basic, Completer, Context, Lambda, Suggester};
+        use crate::test::mock::Context as Mock;
+
+        #[test]
+        fn lambdas_can_bu_used_for_both_completions() {
+            let lambda = Lambda::from(|_: &dyn Context| None);
+            assert_eq!(lambda.complete_for(&Mock::empty()), None);
+
+            let lambda = Lambda::from(|_: &dyn Context| vec![]);
+            assert!(lambda.suggest_for(&Mock::empty()).is_empty());
+        }
+
+        #[test]
+        fn basic_lambda_completer() {
+            let basic = Basic::new(&["zz", "b3", "b2"]);
+            let lambda = Lambda::from(|c: &dyn Context| basic.complete_for(c));
+            let expected = ['3'];
+            assert_eq!(lambda.complete_for(&Mock::from("b")), Some(&expected[..]));
+        }
+
+        #[test]
+        fn basic_lambda_suggester() {
+            let basic = Basic::new(&["a", "b", "c"]);
+            let lambda = Lambda::from(|c: &dyn Context| basic.suggest_for(c));
+            let options = [['a'], ['b'], ['c']];
+            let expected = vec![&options[0][..], &options[1][..], &options[2][..]];
+            assert_eq!(&lambda.suggest_for(&Mock::empty()), &expected);
+            assert_eq!(&lambda.suggest_for(&Mock::from("a")), &expected);
+            assert_eq!(&lambda.suggest_for(&Mock::from("z")), &expected);
+        }
+    }
+
+    mod owned {
+        use super::super::{Basic, Completer, Context, Suggester};
+        use crate::test::mock::Context as Mock;
+        use std::borrow::Cow;
+
+        struct Reversed;
+
+        impl Completer for Reversed {
+            fn completion_for(&self, context: &dyn Context) -> Option<Cow<'_, [char]>> {
+                Some(Cow::Owned(context.buffer().iter().rev().copied().collect()))
+            }
+        }
+
+        impl Suggester for Reversed {
+            fn suggestions_for(&self, context: &dyn Context) -> Vec<Cow<'_, [char]>> {
+                vec![Cow::Owned(context.buffer().iter().rev().copied().collect())]
+            }
+        }
+
+        #[test]
+        fn computed_results_are_owned() {
+            let expected = ['c', 'b', 'a'];
+            assert_eq!(
+                Reversed.completion_for(&Mock::from("abc")).as_deref(),
+                Some(&expected[..])
+            );
+            assert_eq!(
+                Reversed.suggestions_for(&Mock::from("abc")),
+                vec![Cow::Borrowed(&expected[..])]
+            );
+            assert_eq!(Reversed.complete_for(&Mock::from("abc")), None);
+        }
+
+        #[test]
+        fn borrowed_results_are_the_default() {
+            let basic = Basic::new(&["abcd"]);
+            let expected = ['d'];
+            assert_eq!(
+                basic.completion_for(&Mock::from("abc")),
+                Some(Cow::Borrowed(&expected[..]))
+            );
+            assert_eq!(basic.suggestions_for(&Mock::empty()).len(), 1);
+        }
+    }
+}
@@ -0,0 +1,414 @@
+use super::{Candidate, Completer, Context, Suggester};
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+/// Completes the word under the cursor as a filesystem path.
+///
+/// The word is split at the last `/`: the entries of the directory before it that start with
+/// the text after it are the candidates. A leading `~` stands for the home directory, and
+/// relative paths start from the current directory, unless set with [`relative_to`].
+///
+/// Spaces and quotes in the candidates are escaped with a backslash, unless the word starts
+/// with a quote, in which case the quote is closed once a file is completed. Directories are
+/// completed with a trailing `/`, so that their entries can be completed next. Hidden entries
+/// are only offered if the word after the last `/` starts with a `.`.
+///
+/// * As a [`Completer`], the text common to all the candidates is completed in-line, as long
+///   as the cursor is at the end of the buffer.
+/// * As a [`Suggester`], each candidate replaces the word under the cursor.
+///
+/// # Example
+///
+/// ```no_run
+/// use rucline::completion::Paths;
+/// use rucline::Prompt;
+///
+/// let prompt = Prompt::from("$ ")
+///     .completer(Paths::new())
+///     .suggester(Paths::new().extensions(&["rs", "toml"]));
+/// ```
+///
+/// [`Completer`]: trait.Completer.html
+/// [`Suggester`]: trait.Suggester.html
+/// [`relative_to`]: #method.relative_to
+#[derive(Debug, Default, Clone)]
+pub struct Paths {
+    directories_only: bool,
+    extensions: Option<Vec<String>>,
+    root: Option<PathBuf>,
+}
+
+/// An entry of the directory being completed.
+struct Entry {
+    name: String,
+    is_directory: bool,
+}
+
+/// The word under the cursor, as typed and as the path it stands for.
+struct Word {
+    /// The word as typed, up to and including its last `/`
+    directory: String,
+    /// The path the word stands for, up to and including its last `/`
+    directory_path: String,
+    /// The path the word stands for, after its last `/`
+    prefix: String,
+    /// The quote that opens the word, if any
+    quote: Option<char>,
+}
+
+impl Paths {
+    /// Creates a new path completer, relative to the current directory, offering every
+    /// directory and file.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Controls if only directories are offered.
+    ///
+    /// # Arguments
+    ///
+    /// * `directories_only` - If only directories should be offered
+    #[must_use]
+    pub fn directories_only(mut self, directories_only: bool) -> Self {
+        self.directories_only = directories_only;
+        self
+    }
+
+    /// Only offers files with one of the given extensions. Directories are still offered, so
+    /// that files inside them can be reached.
+    ///
+    /// # Arguments
+    ///
+    /// * `extensions` - The extensions to allow, without the leading `.`, e.g. `"rs"`
+    #[must_use]
+    pub fn extensions(mut self, extensions: &[&str]) -> Self {
+        self.extensions = Some(extensions.iter().map(|e| String::from(*e)).collect());
+        self
+    }
+
+    /// Sets the directory that relative paths start from, instead of the current directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The directory that relative paths start from
+    #[must_use]
+    pub fn relative_to(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = Some(root.into());
+        self
+    }
+
+    /// Lists the entries that may complete `word`, sorted by name.
+    fn entries(&self, word: &Word) -> Vec<Entry> {
+        let home = home_directory();
+        let directory = expand_home(&word.directory_path, home.as_deref());
+        let directory = match &self.root {
+            Some(root) if directory.is_relative() => root.join(directory),
+            _ => directory,
+        };
+        let directory = if directory.as_os_str().is_empty() {
+            PathBuf::from(".")
+        } else {
+            directory
+        };
+
+        let Ok(entries) = std::fs::read_dir(directory) else {
+            return Vec::new();
+        };
+
+        let mut entries = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                let is_directory = entry.path().is_dir();
+                Some(Entry { name, is_directory })
+            })
+            .filter(|entry| entry.name.starts_with(&word.prefix))
+            .filter(|entry| word.prefix.starts_with('.') || !entry.name.starts_with('.'))
+            .filter(|entry| self.accepts(entry))
+            .collect::<Vec<_>>();
+
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        entries
+    }
+
+    fn accepts(&self, entry: &Entry) -> bool {
+        if entry.is_directory {
+            return true;
+        }
+
+        if self.directories_only {
+            return false;
+        }
+
+        self.extensions.as_ref().is_none_or(|extensions| {
+            Path::new(&entry.name)
+                .extension()
+                .and_then(std::ffi::OsStr::to_str)
+                .is_some_and(|extension| extensions.iter().any(|e| e == extension))
+        })
+    }
+}
+
+impl Completer for Paths {
+    fn completion_for(&self, context: &dyn Context) -> Option<Cow<'_, [char]>> {
+        // In-line completions are always appended to the end of the buffer
+        if context.cursor() != context.buffer().len() {
+            return None;
+        }
+
+        let word = Word::under_cursor(context);
+        let entries = self.entries(&word);
+        let (first, rest) = entries.split_first()?;
+
+        let mut completion = if rest.is_empty() {
+            let mut name = first.name[word.prefix.len()..].to_string();
+            if first.is_directory {
+                name.push('/');
+            }
+            escape(&name, word.quote)
+        } else {
+            let common = rest.iter().fold(first.name.as_str(), |common, entry| {
+                let length = common
+                    .char_indices()
+                    .zip(entry.name.chars())
+                    .find(|((_, a), b)| a != b)
+                    .map_or(common.len().min(entry.name.len()), |((i, _), _)| i);
+                &common[..length]
+            });
+            escape(&common[word.prefix.len()..], word.quote)
+        };
+
+        // A single file ends the word, so the quote can be closed
+        if let (Some(quote), true) = (word.quote, rest.is_empty() && !first.is_directory) {
+            completion.push(quote);
+        }
+
+        if completion.is_empty() {
+            None
+        } else {
+            Some(Cow::Owned(completion.chars().collect()))
+        }
+    }
+}
+
+impl Suggester for Paths {
+    fn candidates_for(&self, context: &dyn Context) -> Vec<Candidate<'_>> {
+        let word = Word::under_cursor(context);
+        let span = context.word_start()..context.word_end();
+
+        self.entries(&word)
+            .into_iter()
+            .map(|entry| {
+                let mut replacement = word.directory.clone();
+                if let Some(quote) = word.quote {
+                    if replacement.is_empty() {
+                        replacement.push(quote);
+                    }
+                }
+                replacement.push_str(&escape(&entry.name, word.quote));
+                if entry.is_directory {
+                    replacement.push('/');
+                } else if let Some(quote) = word.quote {
+                    replacement.push(quote);
+                }
+
+                Candidate::new(replacement.chars().collect::<Vec<_>>(), span.clone())
+            })
+            .collect()
+    }
+}
+
+impl Word {
+    /// Takes the part of the word under the cursor that comes before the cursor.
+    fn under_cursor(context: &dyn Context) -> Self {
+        let start = context.word_start().min(context.cursor());
+        let raw = &context.buffer()[start..context.cursor()];
+        let quote = match raw.first() {
+            Some(c @ ('"' | '\'')) => Some(*c),
+            _ => None,
+        };
+
+        // Split at the last `/`, keeping the typed directory as is
+        let split = raw
+            .iter()
+            .rposition(|c| *c == '/')
+            .map_or(0, |index| index + 1);
+        let directory = raw[..split].iter().collect::<String>();
+
+        let path = context.current_word().into_iter().collect::<String>();
+        let path_split = path.rfind('/').map_or(0, |index| index + 1);
+
+        Self {
+            directory,
+            directory_path: path[..path_split].to_string(),
+            prefix: path[path_split..].to_string(),
+            quote,
+        }
+    }
+}
+
+/// Escapes the characters of `name` that would otherwise end or change the word.
+fn escape(name: &str, quote: Option<char>) -> String {
+    let mut escaped = String::with_capacity(name.len());
+
+    for c in name.chars() {
+        // Nothing can be escaped within single quotes
+        let needs_escape = match quote {
+            Some('\'') => false,
+            Some(quote) => c == quote || c == '\\',
+            None => c.is_whitespace() || matches!(c, '\\' | '"' | '\''),
+        };
+        if needs_escape {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+fn home_directory() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/// Replaces a leading `~` in `path` with the `home` directory, if known.
+fn expand_home(path: &str, home: Option<&Path>) -> PathBuf {
+    match (path.strip_prefix('~'), home) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+            home.join(rest.trim_start_matches('/'))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{expand_home, Paths};
+    use crate::completion::{Completer, Suggester};
+    use crate::test::mock::Context as Mock;
+    use std::path::{Path, PathBuf};
+
+    /// A directory with a few entries, removed when dropped.
+    struct Fixture(PathBuf);
+
+    impl Fixture {
+        fn new(name: &str) -> Self {
+            let root =
+                std::env::temp_dir().join(format!("rucline-paths-{}-{}", name, std::process::id()));
+            std::fs::create_dir_all(root.join("alpine/peak")).unwrap();
+            for file in &["alpha.txt", "my file.rs", ".hidden", "alpine/base.rs"] {
+                std::fs::write(root.join(file), "").unwrap();
+            }
+            Self(root)
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn complete(paths: &Paths, buffer: &str) -> Option<String> {
+        paths
+            .completion_for(&Mock::from(buffer))
+            .map(|completion| completion.iter().collect())
+    }
+
+    fn suggest(paths: &Paths, buffer: &str) -> Vec<String> {
+        let context = Mock::from(buffer);
+        paths
+            .candidates_for(&context)
+            .iter()
+            .map(|candidate| candidate.apply(&context.buffer).0.iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn complete_common_prefix() {
+        let fixture = Fixture::new("complete");
+        let paths = Paths::new().relative_to(&fixture.0);
+
+        assert_eq!(complete(&paths, "cat a"), Some(String::from("lp")));
+        assert_eq!(complete(&paths, "cat alpi"), Some(String::from("ne/")));
+        assert_eq!(
+            complete(&paths, "cat alpine/b"),
+            Some(String::from("ase.rs"))
+        );
+        assert_eq!(complete(&paths, "cat alpine/"), None);
+        assert_eq!(complete(&paths, "cat m"), Some(String::from("y\\ file.rs")));
+        assert_eq!(
+            complete(&paths, "cat \"m"),
+            Some(String::from("y file.rs\""))
+        );
+        assert_eq!(complete(&paths, "cat z"), None);
+    }
+
+    #[test]
+    fn suggest_replacements() {
+        let fixture = Fixture::new("suggest");
+        let paths = Paths::new().relative_to(&fixture.0);
+
+        assert_eq!(
+            suggest(&paths, "cat al"),
+            vec!["cat alpha.txt", "cat alpine/"]
+        );
+        assert_eq!(
+            suggest(&paths, "cat "),
+            vec!["cat alpha.txt", "cat alpine/", "cat my\\ file.rs"]
+        );
+        assert_eq!(suggest(&paths, "cat ."), vec!["cat .hidden"]);
+        assert_eq!(suggest(&paths, "cat my\\ "), vec!["cat my\\ file.rs"]);
+        assert_eq!(
+            suggest(&paths, "cd alpine/"),
+            vec!["cd alpine/base.rs", "cd alpine/peak/"]
+        );
+    }
+
+    #[test]
+    fn replace_the_whole_word() {
+        let fixture = Fixture::new("mid-word");
+        let paths = Paths::new().relative_to(&fixture.0);
+
+        // The cursor is after `cat al`, inside `alxyz`
+        let mut context = Mock::from("cat alxyz end");
+        context.cursor = 6;
+        let candidates = paths.candidates_for(&context);
+
+        assert_eq!(candidates[0].span(), 4..9);
+        assert_eq!(
+            candidates[0]
+                .apply(&context.buffer)
+                .0
+                .iter()
+                .collect::<String>(),
+            "cat alpha.txt end"
+        );
+    }
+
+    #[test]
+    fn filter_entries() {
+        let fixture = Fixture::new("filter");
+
+        let directories = Paths::new().directories_only(true).relative_to(&fixture.0);
+        assert_eq!(suggest(&directories, "cd "), vec!["cd alpine/"]);
+
+        let sources = Paths::new().extensions(&["rs"]).relative_to(&fixture.0);
+        assert_eq!(
+            suggest(&sources, "vi "),
+            vec!["vi alpine/", "vi my\\ file.rs"]
+        );
+    }
+
+    #[test]
+    fn expand_tilde() {
+        let home = Path::new("/home/user");
+        assert_eq!(expand_home("~/", Some(home)), home);
+        assert_eq!(expand_home("~/docs/", Some(home)), home.join("docs/"));
+        assert_eq!(expand_home("~user/", Some(home)), Path::new("~user/"));
+        assert_eq!(expand_home("~/", None), Path::new("~/"));
+    }
+}
@@ -1,6 +1,13 @@
 //! Provides a [`Context`] to pass into the customization hook calls.
 //!
+//! The context also splits the buffer into words with [`tokenize`], so that every hook agrees
+//! on where a word starts and ends. Words are separated by whitespace, as in a shell:
+//! * A backslash escapes the character after it.
+//! * Single quotes keep everything up to the closing quote as is.
+//! * Double quotes keep everything up to the closing quote, except for backslash escapes.
+//!
 //! [`Context`]: trait.Context.html
+//! [`tokenize`]: fn.tokenize.html
 
 /// The context of the prompt buffer.
 ///
@@ -16,4 +23,214 @@ pub trait Context {
     ///
     /// [`buffer`]: trait.Context.html#tymethod.buffer
     fn cursor(&self) -> usize;
+    /// The selected region of the [`buffer`], between the mark and the cursor, if any.
+    ///
+    /// [`buffer`]: trait.Context.html#tymethod.buffer
+    fn selection(&self) -> Option<&[char]> {
+        None
+    }
+    /// The words of the [`buffer`], as split by [`tokenize`].
+    ///
+    /// [`buffer`]: trait.Context.html#tymethod.buffer
+    /// [`tokenize`]: fn.tokenize.html
+    fn tokens(&self) -> Vec<Token> {
+        tokenize(self.buffer())
+    }
+    /// The index in the [`tokens`] of the word under the cursor.
+    ///
+    /// When the cursor is not touching a word, this is the index a word typed at the cursor
+    /// would have, e.g. `1` for `git |`.
+    ///
+    /// [`tokens`]: trait.Context.html#method.tokens
+    fn argument_index(&self) -> usize {
+        let (index, _) = under_cursor(&self.tokens(), self.cursor());
+        index
+    }
+    /// The index of the first character of the word under the cursor, or of the cursor if it
+    /// is not touching a word.
+    fn word_start(&self) -> usize {
+        let (_, span) = under_cursor(&self.tokens(), self.cursor());
+        span.start
+    }
+    /// The index after the last character of the word under the cursor, or of the cursor if it
+    /// is not touching a word.
+    fn word_end(&self) -> usize {
+        let (_, span) = under_cursor(&self.tokens(), self.cursor());
+        span.end
+    }
+    /// The part of the word under the cursor that comes before the cursor, without quotes and
+    /// escapes.
+    fn current_word(&self) -> Vec<char> {
+        let start = self.word_start().min(self.cursor());
+        tokenize(&self.buffer()[start..self.cursor()])
+            .pop()
+            .map_or_else(Vec::new, |token| token.text)
+    }
+}
+
+/// A word of the buffer.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Token {
+    span: std::ops::Range<usize>,
+    text: Vec<char>,
+}
+
+impl Token {
+    /// The span of the word in the buffer, including its quotes and escapes.
+    #[must_use]
+    pub fn span(&self) -> std::ops::Range<usize> {
+        self.span.clone()
+    }
+
+    /// The word without its quotes and escapes.
+    #[must_use]
+    pub fn text(&self) -> &[char] {
+        &self.text
+    }
+}
+
+/// Splits `buffer` into words at whitespace that is neither quoted nor escaped.
+///
+/// A quote that is not closed extends to the end of the buffer.
+///
+/// # Example
+///
+/// ```
+/// use rucline::context::tokenize;
+///
+/// let buffer = "cp 'my file' your\\ file".chars().collect::<Vec<_>>();
+/// let tokens = tokenize(&buffer);
+///
+/// assert_eq!(tokens.len(), 3);
+/// assert_eq!(tokens[1].span(), 3..12);
+/// assert_eq!(tokens[1].text().iter().collect::<String>(), "my file");
+/// assert_eq!(tokens[2].text().iter().collect::<String>(), "your file");
+/// ```
+#[must_use]
+pub fn tokenize(buffer: &[char]) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut current: Option<Token> = None;
+    let mut quote = None;
+    let mut escaped = false;
+
+    for (index, c) in buffer.iter().copied().enumerate() {
+        if current.is_none() && c.is_whitespace() {
+            continue;
+        }
+
+        let token = current.get_or_insert_with(|| Token {
+            span: index..index,
+            text: Vec::new(),
+        });
+        token.span.end = index + 1;
+
+        if escaped {
+            escaped = false;
+            token.text.push(c);
+        } else if c == '\\' && quote != Some('\'') {
+            escaped = true;
+        } else if Some(c) == quote {
+            quote = None;
+        } else if quote.is_none() && (c == '\'' || c == '"') {
+            quote = Some(c);
+        } else if quote.is_none() && c.is_whitespace() {
+            token.span.end = index;
+            tokens.extend(current.take());
+        } else {
+            token.text.push(c);
+        }
+    }
+
+    tokens.extend(current);
+    tokens
+}
+
+/// Finds the index and span of the token touching the `cursor`, or where one would be.
+fn under_cursor(tokens: &[Token], cursor: usize) -> (usize, std::ops::Range<usize>) {
+    tokens
+        .iter()
+        .position(|token| token.span.start <= cursor && cursor <= token.span.end)
+        .map_or_else(
+            || {
+                let index = tokens
+                    .iter()
+                    .take_while(|token| token.span.end < cursor)
+                    .count();
+                (index, cursor..cursor)
+            },
+            |index| (index, tokens[index].span()),
+        )
+}
+
+#[cfg(test)]
+mod test {
+    use super::{tokenize, Context};
+    use crate::test::mock::Context as Mock;
+
+    fn words(buffer: &str) -> Vec<(std::ops::Range<usize>, String)> {
+        tokenize(&buffer.chars().collect::<Vec<_>>())
+            .into_iter()
+            .map(|token| (token.span(), token.text().iter().collect()))
+            .collect()
+    }
+
+    fn at(buffer: &str, cursor: usize) -> Mock {
+        Mock {
+            buffer: buffer.chars().collect(),
+            cursor,
+        }
+    }
+
+    #[test]
+    fn split_at_whitespace() {
+        assert_eq!(
+            words("  a  bc\td "),
+            vec![
+                (2..3, String::from("a")),
+                (5..7, String::from("bc")),
+                (8..9, String::from("d"))
+            ]
+        );
+        assert!(words("   ").is_empty());
+    }
+
+    #[test]
+    fn honor_quotes_and_escapes() {
+        assert_eq!(
+            words("a  'b c' d\\ e\"f\" "),
+            vec![
+                (0..1, String::from("a")),
+                (3..8, String::from("b c")),
+                (9..16, String::from("d ef")),
+            ]
+        );
+        assert_eq!(words(r#"'a\b' "a\"b""#)[0].1, r"a\b");
+        assert_eq!(words(r#"'a\b' "a\"b""#)[1].1, "a\"b");
+        assert_eq!(
+            words("'open quote"),
+            vec![(0..11, String::from("open quote"))]
+        );
+    }
+
+    #[test]
+    fn find_word_under_cursor() {
+        let context = at("git che --all", 6);
+        assert_eq!(context.argument_index(), 1);
+        assert_eq!(context.word_start(), 4);
+        assert_eq!(context.word_end(), 7);
+        assert_eq!(context.current_word(), vec!['c', 'h']);
+
+        let context = Mock::from("git ");
+        assert_eq!(context.argument_index(), 1);
+        assert_eq!(context.word_start(), 4);
+        assert_eq!(context.word_end(), 4);
+        assert!(context.current_word().is_empty());
+
+        let context = Mock::from("cat 'my fi");
+        assert_eq!(context.argument_index(), 1);
+        assert_eq!(context.word_start(), 4);
+        assert_eq!(context.current_word(), vec!['m', 'y', ' ', 'f', 'i']);
+
+        assert_eq!(Mock::empty().argument_index(), 0);
+    }
 }
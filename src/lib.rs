@@ -83,6 +83,7 @@
 //! [`events`]: actions/type.Event.html
 //! [`prompt`]: prompt/index.html
 pub mod actions;
+pub mod clipboard;
 pub mod completion;
 pub mod context;
 pub mod prompt;
@@ -1,4 +1,4 @@
-use crate::actions::{Direction, Range, Scope};
+use crate::actions::{Case, CharSearch, Direction, Range, Scope, WordBoundary};
 
 use super::CharString;
 
@@ -10,6 +10,16 @@ use super::CharString;
 pub(super) struct Buffer {
     chars: CharString,
     cursor: usize,
+    mark: Option<Mark>,
+}
+
+/// The end of the selected region that stays in place while the cursor moves.
+#[derive(Copy, Clone)]
+struct Mark {
+    position: usize,
+    /// Whether the mark was set by extending a selection, in which case moving the cursor
+    /// without extending drops it
+    temporary: bool,
 }
 
 impl Default for Buffer {
@@ -17,6 +27,7 @@ impl Default for Buffer {
         Self {
             chars: CharString::new(),
             cursor: 0,
+            mark: None,
         }
     }
 }
@@ -33,6 +44,54 @@ impl Buffer {
         self.cursor
     }
 
+    /// Puts the cursor at `cursor`, limited to the end of the buffer.
+    #[inline]
+    pub(super) fn set_cursor(&mut self, cursor: usize) {
+        self.cursor = cursor.min(self.chars.len());
+    }
+
+    /// Sets the mark at the cursor, starting a region that follows the cursor as it moves.
+    #[inline]
+    pub(super) fn set_mark(&mut self) {
+        self.mark = Some(Mark {
+            position: self.cursor,
+            temporary: false,
+        });
+    }
+
+    /// Removes the mark, and with it the selected region.
+    #[inline]
+    pub(super) fn clear_mark(&mut self) {
+        self.mark = None;
+    }
+
+    /// Returns the range between the mark and the cursor, if it is not empty.
+    pub(super) fn selection(&self) -> Option<std::ops::Range<usize>> {
+        let mark = self.mark?.position.min(self.chars.len());
+        match mark.cmp(&self.cursor) {
+            std::cmp::Ordering::Less => Some(mark..self.cursor),
+            std::cmp::Ordering::Greater => Some(self.cursor..mark),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    /// Removes the selected region from the buffer, returning it.
+    pub(super) fn take_selection(&mut self) -> Option<Vec<char>> {
+        self.selection().map(|range| self.drain(range))
+    }
+
+    /// Moves the cursor like [`move_cursor`](#method.move_cursor), extending the selected
+    /// region. If there is no mark, one is set where the cursor was.
+    pub(super) fn select(&mut self, range: Range, direction: Direction, words: &WordBoundary) {
+        if self.mark.is_none() {
+            self.mark = Some(Mark {
+                position: self.cursor,
+                temporary: true,
+            });
+        }
+        self.cursor = self.cursor_target(range, direction, words);
+    }
+
     /// Puts the cursor at the end of the buffer
     /// This is short-hand for `move_cursor(Range::Line, Direction::Forward)`
     #[inline]
@@ -46,17 +105,11 @@ impl Buffer {
         self.chars.len()
     }
 
-    /// Clears the buffer and sets the cursor back to zero.
-    #[inline]
-    pub(super) fn clear(&mut self) {
-        self.chars.clear();
-        self.cursor = 0;
-    }
-
     /// Inserts a single character to the buffer at the cursor position and increments
     /// the cursor by one.
     #[inline]
     pub(super) fn write(&mut self, c: char) {
+        self.mark = None;
         self.chars.insert(self.cursor, c);
         self.cursor += 1;
     }
@@ -65,91 +118,176 @@ impl Buffer {
     /// the cursor by the length of `string`.
     #[inline]
     pub(super) fn write_str(&mut self, string: &[char]) {
+        self.mark = None;
         self.chars.insert_str(self.cursor, string);
         self.cursor += string.len();
     }
 
+    /// Removes the characters in `range` from the buffer, returning them, and puts the cursor
+    /// at the start of the range.
+    pub(super) fn drain(&mut self, range: std::ops::Range<usize>) -> Vec<char> {
+        let removed = self.chars[range.clone()].to_vec();
+        self.mark = None;
+        self.cursor = range.start;
+        self.chars.drain(range);
+        removed
+    }
+
     /// Deletes the given [`scope`](../../actions/enum.Scope.html) from this buffer
-    /// and updates the cursor accordingly.
-    pub(super) fn delete(&mut self, scope: Scope) {
+    /// and updates the cursor accordingly, returning the deleted characters.
+    pub(super) fn delete(&mut self, scope: Scope, words: &WordBoundary) -> Vec<char> {
         use Direction::{Backward, Forward};
         use Range::{Line, Single, Word};
         use Scope::{Relative, WholeLine, WholeWord};
 
-        match scope {
-            Relative(Single, Backward) => {
-                if self.cursor > 0 {
-                    self.chars.remove(self.cursor - 1);
-                    self.cursor -= 1;
-                }
-            }
-            Relative(Single, Forward) => {
-                if self.cursor < self.chars.len() {
-                    self.chars.remove(self.cursor);
-                }
-            }
+        let range = match scope {
+            Relative(Single, Backward) => self.cursor.saturating_sub(1)..self.cursor,
+            Relative(Single, Forward) => self.cursor..(self.cursor + 1).min(self.chars.len()),
             Relative(Word, Backward) => {
-                let index = super::navigation::previous_word(self.cursor, &self.chars);
-                self.chars.drain(index..self.cursor);
-                self.cursor = index;
+                super::navigation::previous_word(self.cursor, &self.chars, words)..self.cursor
             }
             Relative(Word, Forward) => {
-                let index = super::navigation::next_word(self.cursor, &self.chars);
-                self.chars.drain(self.cursor..index);
-            }
-            Relative(Line, Backward) => {
-                self.chars.drain(0..self.cursor);
-                self.cursor = 0;
-            }
-            Relative(Line, Forward) => {
-                self.chars.drain(self.cursor..self.chars.len());
+                self.cursor..super::navigation::next_word(self.cursor, &self.chars, words)
             }
+            Relative(Line, Backward) => 0..self.cursor,
+            Relative(Line, Forward) => self.cursor..self.chars.len(),
             WholeWord => {
-                let mut start = super::navigation::previous_word_end(self.cursor, &self.chars);
-                let end = super::navigation::next_word(self.cursor, &self.chars);
+                let mut start =
+                    super::navigation::previous_word_end(self.cursor, &self.chars, words);
+                let end = super::navigation::next_word(self.cursor, &self.chars, words);
 
-                // If in the middle of the string, save one trailing space
-                if start > 0 {
+                // If in the middle of the string, save one trailing separator
+                if start > 0 && words.is_separator(self.chars[start]) {
                     start += 1;
                 }
 
-                self.chars.drain(start..end);
-                self.cursor = start;
+                start..end
             }
-            WholeLine => self.clear(),
+            WholeLine => 0..self.chars.len(),
+        };
+
+        self.drain(range)
+    }
+
+    /// Moves the cursor to the next or previous occurrence of `target`, as defined by
+    /// `search`. If there is no occurrence, the cursor does not move.
+    pub(super) fn move_to_char(&mut self, search: CharSearch, target: char) {
+        if let Some(position) = self.search_char(search, target) {
+            self.cursor = match search {
+                CharSearch::Find(_) => position,
+                CharSearch::Till(Direction::Forward) => position - 1,
+                CharSearch::Till(Direction::Backward) => position + 1,
+            };
         }
     }
 
+    /// Deletes from the cursor to the next or previous occurrence of `target`, as defined by
+    /// `search`, returning the deleted characters. The occurrence itself is only deleted by
+    /// [`Find`](../../actions/enum.CharSearch.html#variant.Find).
+    pub(super) fn delete_to_char(&mut self, search: CharSearch, target: char) -> Vec<char> {
+        let range = match (search, self.search_char(search, target)) {
+            (_, None) => return Vec::new(),
+            (CharSearch::Find(Direction::Forward), Some(position)) => self.cursor..position + 1,
+            (CharSearch::Till(Direction::Forward), Some(position)) => self.cursor..position,
+            (CharSearch::Find(Direction::Backward), Some(position)) => position..self.cursor,
+            (CharSearch::Till(Direction::Backward), Some(position)) => position + 1..self.cursor,
+        };
+
+        self.drain(range)
+    }
+
+    fn search_char(&self, search: CharSearch, target: char) -> Option<usize> {
+        let direction = match search {
+            CharSearch::Find(direction) | CharSearch::Till(direction) => direction,
+        };
+        super::navigation::find_char(self.cursor, &self.chars, target, direction)
+    }
+
+    /// Swaps the character before the cursor with the one under it and moves the cursor
+    /// forward. At the end of the buffer, the last two characters are swapped instead.
+    pub(super) fn transpose_chars(&mut self) {
+        if self.cursor == 0 || self.chars.len() < 2 {
+            return;
+        }
+
+        if self.cursor == self.chars.len() {
+            self.cursor -= 1;
+        }
+
+        self.mark = None;
+        self.chars.swap(self.cursor - 1, self.cursor);
+        self.cursor += 1;
+    }
+
+    /// Swaps the word before the cursor with the word under or after it, and moves the cursor
+    /// past both. At the end of the buffer, the last two words are swapped instead.
+    pub(super) fn transpose_words(&mut self, words: &WordBoundary) {
+        use super::navigation::{end_of_word, previous_word};
+
+        let second_end = end_of_word(self.cursor, &self.chars, words);
+        let second_start = previous_word(second_end, &self.chars, words);
+        let second_end = end_of_word(second_start, &self.chars, words);
+        let first_start = previous_word(second_start, &self.chars, words);
+        let first_end = end_of_word(first_start, &self.chars, words);
+
+        // There must be two distinct words
+        if first_start == second_start || first_end > second_start {
+            return;
+        }
+
+        let mut swapped = self.chars[second_start..second_end].to_vec();
+        swapped.extend_from_slice(&self.chars[first_end..second_start]);
+        swapped.extend_from_slice(&self.chars[first_start..first_end]);
+
+        self.drain(first_start..second_end);
+        self.write_str(&swapped);
+    }
+
+    /// Changes the case of the characters from the cursor to the end of the word under or
+    /// after it, and moves the cursor to the end of that word.
+    pub(super) fn change_case(&mut self, case: Case, words: &WordBoundary) {
+        let end = super::navigation::end_of_word(self.cursor, &self.chars, words);
+        let mut capitalize = case == Case::Capitalize;
+
+        let changed = self.chars[self.cursor..end]
+            .iter()
+            .flat_map(|c| {
+                let upper = case == Case::Upper || (capitalize && c.is_alphanumeric());
+                capitalize &= !c.is_alphanumeric();
+                if upper {
+                    c.to_uppercase().collect::<Vec<_>>()
+                } else {
+                    c.to_lowercase().collect::<Vec<_>>()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        self.drain(self.cursor..end);
+        self.write_str(&changed);
+    }
+
     /// Moves the cursor by [`range`](../../actions/enum.Range.html)
-    pub(super) fn move_cursor(&mut self, range: Range, direction: Direction) {
+    ///
+    /// A mark set by [`select`](#method.select) is dropped, while one set by
+    /// [`set_mark`](#method.set_mark) keeps selecting up to the cursor.
+    pub(super) fn move_cursor(&mut self, range: Range, direction: Direction, words: &WordBoundary) {
+        if self.mark.is_some_and(|mark| mark.temporary) {
+            self.mark = None;
+        }
+        self.cursor = self.cursor_target(range, direction, words);
+    }
+
+    fn cursor_target(&self, range: Range, direction: Direction, words: &WordBoundary) -> usize {
         use Direction::{Backward, Forward};
         use Range::{Line, Single, Word};
 
         match (range, direction) {
-            (Single, Backward) => {
-                if self.cursor > 0 {
-                    self.cursor -= 1;
-                }
-            }
-            (Single, Forward) => {
-                if self.cursor < self.chars.len() {
-                    self.cursor += 1;
-                }
-            }
-            (Word, Backward) => {
-                self.cursor = super::navigation::previous_word(self.cursor, &self.chars);
-            }
-            (Word, Forward) => {
-                self.cursor = super::navigation::next_word(self.cursor, &self.chars);
-            }
-            (Line, Backward) => {
-                self.cursor = 0;
-            }
-            (Line, Forward) => {
-                if self.cursor < self.chars.len() {
-                    self.cursor = self.chars.len();
-                }
-            }
+            (Single, Backward) => self.cursor.saturating_sub(1),
+            (Single, Forward) => (self.cursor + 1).min(self.chars.len()),
+            (Word, Backward) => super::navigation::previous_word(self.cursor, &self.chars, words),
+            (Word, Forward) => super::navigation::next_word(self.cursor, &self.chars, words),
+            (Line, Backward) => 0,
+            (Line, Forward) => self.chars.len(),
         }
     }
 }
@@ -167,7 +305,11 @@ impl std::convert::From<&[char]> for Buffer {
     fn from(string: &[char]) -> Self {
         let chars = CharString::from(string);
         let cursor = chars.len();
-        Self { chars, cursor }
+        Self {
+            chars,
+            cursor,
+            mark: None,
+        }
     }
 }
 
@@ -179,12 +321,13 @@ impl std::fmt::Display for Buffer {
 
 #[cfg(test)]
 mod test {
-    use super::{Buffer, CharString, Direction, Range, Scope};
+    use super::{Buffer, Case, CharSearch, CharString, Direction, Range, Scope, WordBoundary};
 
     fn build_uut(string: &str) -> Buffer {
         Buffer {
             chars: CharString::from(string),
             cursor: 0,
+            mark: None,
         }
     }
 
@@ -198,25 +341,37 @@ mod test {
 
         // Delete from the middle
         set_cursor(&mut buffer, "asdf b_s  as   v as  bas   asdf");
-        buffer.delete(Scope::Relative(Range::Single, Direction::Forward));
+        buffer.delete(
+            Scope::Relative(Range::Single, Direction::Forward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 6);
         assert_eq!(buffer.chars.to_string(), "asdf bs  as   v as  bas   asdf");
 
         // Delete from the end
         set_cursor(&mut buffer, "asdf bs  as   v as  bas   asd_");
-        buffer.delete(Scope::Relative(Range::Single, Direction::Forward));
+        buffer.delete(
+            Scope::Relative(Range::Single, Direction::Forward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 29);
         assert_eq!(buffer.chars.to_string(), "asdf bs  as   v as  bas   asd");
 
         // Delete from past the end
         set_cursor(&mut buffer, "asdf bs  as   v as  bas   asd_");
-        buffer.delete(Scope::Relative(Range::Single, Direction::Forward));
+        buffer.delete(
+            Scope::Relative(Range::Single, Direction::Forward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 29);
         assert_eq!(buffer.chars.to_string(), "asdf bs  as   v as  bas   asd");
 
         // Delete from the start
         set_cursor(&mut buffer, "_sdf bs  as   v as  bas   asd");
-        buffer.delete(Scope::Relative(Range::Single, Direction::Forward));
+        buffer.delete(
+            Scope::Relative(Range::Single, Direction::Forward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 0);
         assert_eq!(buffer.chars.to_string(), "sdf bs  as   v as  bas   asd");
     }
@@ -227,25 +382,37 @@ mod test {
 
         // Delete from the middle
         set_cursor(&mut buffer, "asdf b_s  as   v as  bas   asdf");
-        buffer.delete(Scope::Relative(Range::Single, Direction::Backward));
+        buffer.delete(
+            Scope::Relative(Range::Single, Direction::Backward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 5);
         assert_eq!(buffer.chars.to_string(), "asdf as  as   v as  bas   asdf");
 
         // Delete from the end
         set_cursor(&mut buffer, "asdf as  as   v as  bas   asd_");
-        buffer.delete(Scope::Relative(Range::Single, Direction::Backward));
+        buffer.delete(
+            Scope::Relative(Range::Single, Direction::Backward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 28);
         assert_eq!(buffer.chars.to_string(), "asdf as  as   v as  bas   asf");
 
         // Delete from past the end
         set_cursor(&mut buffer, "asdf as  as   v as  bas   asf_");
-        buffer.delete(Scope::Relative(Range::Single, Direction::Backward));
+        buffer.delete(
+            Scope::Relative(Range::Single, Direction::Backward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 28);
         assert_eq!(buffer.chars.to_string(), "asdf as  as   v as  bas   as");
 
         // Delete from the start
         set_cursor(&mut buffer, "_sdf as  as   v as  bas   as");
-        buffer.delete(Scope::Relative(Range::Single, Direction::Backward));
+        buffer.delete(
+            Scope::Relative(Range::Single, Direction::Backward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 0);
         assert_eq!(buffer.chars.to_string(), "asdf as  as   v as  bas   as");
     }
@@ -256,37 +423,55 @@ mod test {
 
         // Delete from the middle
         set_cursor(&mut buffer, "as_f bas  as   v as  bas   asdf");
-        buffer.delete(Scope::Relative(Range::Word, Direction::Forward));
+        buffer.delete(
+            Scope::Relative(Range::Word, Direction::Forward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 2);
         assert_eq!(buffer.chars.to_string(), "asbas  as   v as  bas   asdf");
 
         // Delete single letter word
         set_cursor(&mut buffer, "asbas  as   _ as  bas   asdf");
-        buffer.delete(Scope::Relative(Range::Word, Direction::Forward));
+        buffer.delete(
+            Scope::Relative(Range::Word, Direction::Forward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 12);
         assert_eq!(buffer.chars.to_string(), "asbas  as   as  bas   asdf");
 
         // Delete from space
         set_cursor(&mut buffer, "asbas  as _ as  bas   asdf");
-        buffer.delete(Scope::Relative(Range::Word, Direction::Forward));
+        buffer.delete(
+            Scope::Relative(Range::Word, Direction::Forward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 10);
         assert_eq!(buffer.chars.to_string(), "asbas  as as  bas   asdf");
 
         // Delete from the end
         set_cursor(&mut buffer, "asbas  as as  bas   asd_");
-        buffer.delete(Scope::Relative(Range::Word, Direction::Forward));
+        buffer.delete(
+            Scope::Relative(Range::Word, Direction::Forward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 23);
         assert_eq!(buffer.chars.to_string(), "asbas  as as  bas   asd");
 
         // Delete from past the end
         set_cursor(&mut buffer, "asbas  as as  bas   asd_");
-        buffer.delete(Scope::Relative(Range::Word, Direction::Forward));
+        buffer.delete(
+            Scope::Relative(Range::Word, Direction::Forward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 23);
         assert_eq!(buffer.chars.to_string(), "asbas  as as  bas   asd");
 
         // Delete from the start
         set_cursor(&mut buffer, "_sbas  as as  bas   asd");
-        buffer.delete(Scope::Relative(Range::Word, Direction::Forward));
+        buffer.delete(
+            Scope::Relative(Range::Word, Direction::Forward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 0);
         assert_eq!(buffer.chars.to_string(), "as as  bas   asd");
     }
@@ -297,37 +482,55 @@ mod test {
 
         // Delete from the middle
         set_cursor(&mut buffer, "as_f bas  as   v as  bas   asdf");
-        buffer.delete(Scope::Relative(Range::Word, Direction::Backward));
+        buffer.delete(
+            Scope::Relative(Range::Word, Direction::Backward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 0);
         assert_eq!(buffer.chars.to_string(), "df bas  as   v as  bas   asdf");
 
         // Delete single letter word
         set_cursor(&mut buffer, "df bas  as   _ as  bas   asdf");
-        buffer.delete(Scope::Relative(Range::Word, Direction::Backward));
+        buffer.delete(
+            Scope::Relative(Range::Word, Direction::Backward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 8);
         assert_eq!(buffer.chars.to_string(), "df bas  v as  bas   asdf");
 
         // Delete from space
         set_cursor(&mut buffer, "df bas  v as  bas _ asdf");
-        buffer.delete(Scope::Relative(Range::Word, Direction::Backward));
+        buffer.delete(
+            Scope::Relative(Range::Word, Direction::Backward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 14);
         assert_eq!(buffer.chars.to_string(), "df bas  v as    asdf");
 
         // Delete from the end
         set_cursor(&mut buffer, "df bas  v as    asd_");
-        buffer.delete(Scope::Relative(Range::Word, Direction::Backward));
+        buffer.delete(
+            Scope::Relative(Range::Word, Direction::Backward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 16);
         assert_eq!(buffer.chars.to_string(), "df bas  v as    f");
 
         // Delete from past the end
         set_cursor(&mut buffer, "df bas  v as    f_");
-        buffer.delete(Scope::Relative(Range::Word, Direction::Backward));
+        buffer.delete(
+            Scope::Relative(Range::Word, Direction::Backward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 16);
         assert_eq!(buffer.chars.to_string(), "df bas  v as    ");
 
         // Delete from the start
         set_cursor(&mut buffer, "_f bas  v as    ");
-        buffer.delete(Scope::Relative(Range::Word, Direction::Backward));
+        buffer.delete(
+            Scope::Relative(Range::Word, Direction::Backward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 0);
         assert_eq!(buffer.chars.to_string(), "df bas  v as    ");
     }
@@ -338,25 +541,37 @@ mod test {
 
         // Delete from the middle
         set_cursor(&mut buffer, "asdf bas  as   _ as  bas   asdf");
-        buffer.delete(Scope::Relative(Range::Line, Direction::Forward));
+        buffer.delete(
+            Scope::Relative(Range::Line, Direction::Forward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 15);
         assert_eq!(buffer.chars.to_string(), "asdf bas  as   ");
 
         // Delete from the end
         set_cursor(&mut buffer, "asdf bas  as   _");
-        buffer.delete(Scope::Relative(Range::Line, Direction::Forward));
+        buffer.delete(
+            Scope::Relative(Range::Line, Direction::Forward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 15);
         assert_eq!(buffer.chars.to_string(), "asdf bas  as   ");
 
         // Delete from the start
         set_cursor(&mut buffer, "_sdf bas  as   ");
-        buffer.delete(Scope::Relative(Range::Line, Direction::Forward));
+        buffer.delete(
+            Scope::Relative(Range::Line, Direction::Forward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 0);
         assert_eq!(buffer.chars.to_string(), "");
 
         // Delete empty line
         set_cursor(&mut buffer, "_");
-        buffer.delete(Scope::Relative(Range::Line, Direction::Forward));
+        buffer.delete(
+            Scope::Relative(Range::Line, Direction::Forward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 0);
         assert_eq!(buffer.chars.to_string(), "");
     }
@@ -367,25 +582,37 @@ mod test {
 
         // Delete from the middle
         set_cursor(&mut buffer, "asdf bas  as   _ as  bas   asdf");
-        buffer.delete(Scope::Relative(Range::Line, Direction::Backward));
+        buffer.delete(
+            Scope::Relative(Range::Line, Direction::Backward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 0);
         assert_eq!(buffer.chars.to_string(), "v as  bas   asdf");
 
         // Delete from the start
         set_cursor(&mut buffer, "_as  bas   asdf");
-        buffer.delete(Scope::Relative(Range::Line, Direction::Backward));
+        buffer.delete(
+            Scope::Relative(Range::Line, Direction::Backward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 0);
         assert_eq!(buffer.chars.to_string(), "v as  bas   asdf");
 
         // Delete from the end
         set_cursor(&mut buffer, "v as  bas   asdf_");
-        buffer.delete(Scope::Relative(Range::Line, Direction::Backward));
+        buffer.delete(
+            Scope::Relative(Range::Line, Direction::Backward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 0);
         assert_eq!(buffer.chars.to_string(), "");
 
         // Delete empty line
         set_cursor(&mut buffer, "_");
-        buffer.delete(Scope::Relative(Range::Line, Direction::Backward));
+        buffer.delete(
+            Scope::Relative(Range::Line, Direction::Backward),
+            &WordBoundary::Whitespace,
+        );
         assert_eq!(buffer.cursor, 0);
         assert_eq!(buffer.chars.to_string(), "");
     }
@@ -396,36 +623,220 @@ mod test {
 
         // Delete from the middle
         set_cursor(&mut buffer, "as_f bas  as   v as  bas   asdf");
-        buffer.delete(Scope::WholeWord);
+        buffer.delete(Scope::WholeWord, &WordBoundary::Whitespace);
         assert_eq!(buffer.cursor, 0);
         assert_eq!(buffer.chars.to_string(), "bas  as   v as  bas   asdf");
 
         // Delete single letter word
         set_cursor(&mut buffer, "bas  as   _ as  bas   asdf");
-        buffer.delete(Scope::WholeWord);
+        buffer.delete(Scope::WholeWord, &WordBoundary::Whitespace);
         assert_eq!(buffer.cursor, 8);
         assert_eq!(buffer.chars.to_string(), "bas  as as  bas   asdf");
 
         // Delete from space
         set_cursor(&mut buffer, "bas  as as  bas _ asdf");
-        buffer.delete(Scope::WholeWord);
+        buffer.delete(Scope::WholeWord, &WordBoundary::Whitespace);
         assert_eq!(buffer.cursor, 16);
         assert_eq!(buffer.chars.to_string(), "bas  as as  bas asdf");
     }
 
+    #[test]
+    fn move_to_char() {
+        let mut buffer = build_uut("cd /usr/local/bin");
+
+        set_cursor(&mut buffer, "c_ /usr/local/bin");
+        buffer.move_to_char(CharSearch::Find(Direction::Forward), '/');
+        assert_eq!(buffer.cursor, 3);
+
+        // The character under the cursor is skipped
+        buffer.move_to_char(CharSearch::Find(Direction::Forward), '/');
+        assert_eq!(buffer.cursor, 7);
+
+        buffer.move_to_char(CharSearch::Till(Direction::Forward), '/');
+        assert_eq!(buffer.cursor, 12);
+
+        buffer.move_to_char(CharSearch::Find(Direction::Backward), 'u');
+        assert_eq!(buffer.cursor, 4);
+
+        buffer.go_to_end();
+        buffer.move_to_char(CharSearch::Till(Direction::Backward), '/');
+        assert_eq!(buffer.cursor, 14);
+
+        // Missing characters do not move the cursor
+        buffer.move_to_char(CharSearch::Find(Direction::Backward), 'z');
+        assert_eq!(buffer.cursor, 14);
+    }
+
+    #[test]
+    fn delete_to_char() {
+        let mut buffer = build_uut("cd /usr/local/bin");
+
+        set_cursor(&mut buffer, "cd _usr/local/bin");
+        let deleted = buffer.delete_to_char(CharSearch::Find(Direction::Forward), '/');
+        assert_eq!(deleted.iter().collect::<String>(), "/usr/");
+        assert_eq!(buffer.cursor, 3);
+        assert_eq!(buffer.chars.to_string(), "cd local/bin");
+
+        buffer.delete_to_char(CharSearch::Till(Direction::Forward), '/');
+        assert_eq!(buffer.cursor, 3);
+        assert_eq!(buffer.chars.to_string(), "cd /bin");
+
+        buffer.go_to_end();
+        buffer.delete_to_char(CharSearch::Till(Direction::Backward), '/');
+        assert_eq!(buffer.cursor, 4);
+        assert_eq!(buffer.chars.to_string(), "cd /");
+
+        buffer.delete_to_char(CharSearch::Find(Direction::Backward), ' ');
+        assert_eq!(buffer.cursor, 2);
+        assert_eq!(buffer.chars.to_string(), "cd");
+
+        // Missing characters do not delete anything
+        assert!(buffer
+            .delete_to_char(CharSearch::Find(Direction::Backward), 'z')
+            .is_empty());
+        assert_eq!(buffer.chars.to_string(), "cd");
+    }
+
+    #[test]
+    fn transpose_words() {
+        let mut buffer = build_uut("ab cd  ef");
+
+        // Nothing before the first word
+        set_cursor(&mut buffer, "a_ cd  ef");
+        buffer.transpose_words(&WordBoundary::Whitespace);
+        assert_eq!(buffer.cursor, 1);
+        assert_eq!(buffer.chars.to_string(), "ab cd  ef");
+
+        // Drag the previous word past the current one
+        set_cursor(&mut buffer, "ab c_  ef");
+        buffer.transpose_words(&WordBoundary::Whitespace);
+        assert_eq!(buffer.cursor, 5);
+        assert_eq!(buffer.chars.to_string(), "cd ab  ef");
+
+        // From whitespace, the next word is the current one
+        set_cursor(&mut buffer, "cd ab_ ef");
+        buffer.transpose_words(&WordBoundary::Whitespace);
+        assert_eq!(buffer.cursor, 9);
+        assert_eq!(buffer.chars.to_string(), "cd ef  ab");
+
+        // At the end, swap the last two words
+        buffer.chars = CharString::from("cd ef  ab  ");
+        buffer.go_to_end();
+        buffer.transpose_words(&WordBoundary::Whitespace);
+        assert_eq!(buffer.cursor, 9);
+        assert_eq!(buffer.chars.to_string(), "cd ab  ef  ");
+    }
+
+    #[test]
+    fn change_case() {
+        let mut buffer = build_uut("hello wOrLD  ünïcode");
+
+        // From the middle of a word
+        set_cursor(&mut buffer, "he_lo wOrLD  ünïcode");
+        buffer.change_case(Case::Upper, &WordBoundary::Whitespace);
+        assert_eq!(buffer.cursor, 5);
+        assert_eq!(buffer.chars.to_string(), "heLLO wOrLD  ünïcode");
+
+        // From whitespace, the next word is changed
+        buffer.change_case(Case::Lower, &WordBoundary::Whitespace);
+        assert_eq!(buffer.cursor, 11);
+        assert_eq!(buffer.chars.to_string(), "heLLO world  ünïcode");
+
+        buffer.change_case(Case::Capitalize, &WordBoundary::Whitespace);
+        assert_eq!(buffer.cursor, 20);
+        assert_eq!(buffer.chars.to_string(), "heLLO world  Ünïcode");
+
+        // Capitalize the rest of a word
+        set_cursor(&mut buffer, "he_LO world  Ünïcode");
+        buffer.change_case(Case::Capitalize, &WordBoundary::Whitespace);
+        assert_eq!(buffer.cursor, 5);
+        assert_eq!(buffer.chars.to_string(), "heLlo world  Ünïcode");
+
+        // At the end, nothing changes
+        buffer.go_to_end();
+        buffer.change_case(Case::Upper, &WordBoundary::Whitespace);
+        assert_eq!(buffer.cursor, 20);
+        assert_eq!(buffer.chars.to_string(), "heLlo world  Ünïcode");
+    }
+
+    #[test]
+    fn delete_returns_deleted_text() {
+        let mut buffer = build_uut("asdf bas  as");
+
+        set_cursor(&mut buffer, "asdf _as  as");
+        let deleted = buffer.delete(
+            Scope::Relative(Range::Word, Direction::Forward),
+            &WordBoundary::Whitespace,
+        );
+        assert_eq!(deleted.iter().collect::<String>(), "bas  ");
+        assert_eq!(buffer.chars.to_string(), "asdf as");
+
+        set_cursor(&mut buffer, "_sdf as");
+        let deleted = buffer.delete(
+            Scope::Relative(Range::Single, Direction::Backward),
+            &WordBoundary::Whitespace,
+        );
+        assert!(deleted.is_empty());
+    }
+
+    #[test]
+    fn transpose_chars() {
+        let mut buffer = build_uut("abc");
+
+        // Nothing before the cursor
+        buffer.transpose_chars();
+        assert_eq!(buffer.cursor, 0);
+        assert_eq!(buffer.chars.to_string(), "abc");
+
+        // Swap and move forward
+        set_cursor(&mut buffer, "a_c");
+        buffer.transpose_chars();
+        assert_eq!(buffer.cursor, 2);
+        assert_eq!(buffer.chars.to_string(), "bac");
+
+        // Swap the last two at the end
+        buffer.go_to_end();
+        buffer.transpose_chars();
+        assert_eq!(buffer.cursor, 3);
+        assert_eq!(buffer.chars.to_string(), "bca");
+    }
+
+    #[test]
+    fn select() {
+        let mut buffer = build_uut("one two");
+        let words = &WordBoundary::Whitespace;
+
+        // Extending a selection sets a temporary mark
+        buffer.select(Range::Word, Direction::Forward, words);
+        buffer.select(Range::Single, Direction::Forward, words);
+        assert_eq!(buffer.selection(), Some(0..5));
+        buffer.move_cursor(Range::Single, Direction::Backward, words);
+        assert_eq!(buffer.selection(), None);
+
+        // A set mark follows the cursor
+        buffer.set_mark();
+        buffer.move_cursor(Range::Line, Direction::Forward, words);
+        assert_eq!(buffer.selection(), Some(4..7));
+
+        assert_eq!(buffer.take_selection(), Some(vec!['t', 'w', 'o']));
+        assert_eq!(buffer.chars.to_string(), "one ");
+        assert_eq!(buffer.cursor, 4);
+        assert_eq!(buffer.selection(), None);
+    }
+
     #[test]
     fn delete_whole_line() {
         let mut buffer = build_uut("asdf bas  as   v as  bas   asdf");
 
         // Delete from the middle
         set_cursor(&mut buffer, "asdf bas  as   _ as  bas   asdf");
-        buffer.delete(Scope::WholeLine);
+        buffer.delete(Scope::WholeLine, &WordBoundary::Whitespace);
         assert_eq!(buffer.cursor, 0);
         assert_eq!(buffer.chars.to_string(), "");
 
         // Delete empty line
         set_cursor(&mut buffer, "_");
-        buffer.delete(Scope::WholeLine);
+        buffer.delete(Scope::WholeLine, &WordBoundary::Whitespace);
         assert_eq!(buffer.cursor, 0);
         assert_eq!(buffer.chars.to_string(), "");
     }
@@ -13,6 +13,30 @@ impl CharString {
         self.0.len()
     }
 
+    /// Returns the amount of columns this string occupies once printed, ignoring any ANSI
+    /// escape sequences used for styling.
+    pub(super) fn printable_len(&self) -> usize {
+        let mut length = 0;
+        let mut chars = self.0.iter();
+
+        while let Some(c) = chars.next() {
+            if *c == '\x1b' {
+                // Skip the control sequence introducer and everything up to its final byte
+                if chars.next() == Some(&'[') {
+                    for c in &mut chars {
+                        if ('\x40'..='\x7e').contains(c) {
+                            break;
+                        }
+                    }
+                }
+            } else if !c.is_control() {
+                length += 1;
+            }
+        }
+
+        length
+    }
+
     #[inline]
     pub(super) fn insert(&mut self, index: usize, c: char) {
         self.0.insert(index, c);
@@ -30,13 +54,8 @@ impl CharString {
     }
 
     #[inline]
-    pub(super) fn clear(&mut self) {
-        self.0.clear();
-    }
-
-    #[inline]
-    pub(super) fn remove(&mut self, index: usize) {
-        self.0.remove(index);
+    pub(super) fn swap(&mut self, a: usize, b: usize) {
+        self.0.swap(a, b);
     }
 
     #[inline]
@@ -105,12 +124,6 @@ impl std::fmt::Display for CharString {
 #[derive(Copy, Clone)]
 pub(super) struct CharStringView<'a>(&'a [char]);
 
-impl CharStringView<'_> {
-    pub(super) fn as_slice(&self) -> &[char] {
-        self.0
-    }
-}
-
 impl<'a> std::convert::From<&'a [char]> for CharStringView<'a> {
     fn from(data: &'a [char]) -> Self {
         Self(data)
@@ -121,7 +134,7 @@ impl std::ops::Deref for CharStringView<'_> {
     type Target = [char];
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        self.0
     }
 }
 
@@ -134,3 +147,17 @@ impl std::fmt::Display for CharStringView<'_> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::CharString;
+
+    #[test]
+    fn printable_len_ignores_styling() {
+        let plain = CharString::from("My prompt");
+        assert_eq!(plain.printable_len(), 9);
+
+        let styled = CharString::from("\x1b[1;32mMy prompt\x1b[0m");
+        assert_eq!(styled.printable_len(), 9);
+    }
+}
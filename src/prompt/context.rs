@@ -1,16 +1,23 @@
 use super::{
-    navigation, Buffer, CharString, CharStringView, Completer, Direction, Range, Scope, Suggester,
-    Writer,
+    editor, navigation, Buffer, Candidate, Case, CharSearch, CharString, Completer, Direction,
+    Drawn, Event, Keymap, Range, Scope, Suggester, Vi, WordBoundary, Writer,
 };
+use crate::clipboard::{Clipboard, Osc52};
 use crate::Context;
+use std::borrow::Cow;
 
 pub(super) struct ContextImpl<'a> {
-    writer: Writer,
+    writer: Writer<'a>,
     buffer: Buffer,
-    completer: &'a Option<Box<dyn Completer>>,
-    completion: Option<CharStringView<'a>>,
-    suggester: &'a Option<Box<dyn Suggester>>,
+    completer: Option<&'a dyn Completer>,
+    completion: Option<Cow<'a, [char]>>,
+    suggester: Option<&'a dyn Suggester>,
     suggestions: Option<Suggestions<'a>>,
+    filter_menu: bool,
+    clipboard: &'a dyn Clipboard,
+    vi: Option<Vi>,
+    killed: Vec<char>,
+    words: WordBoundary,
 }
 
 impl Context for ContextImpl<'_> {
@@ -23,22 +30,40 @@ impl Context for ContextImpl<'_> {
     fn cursor(&self) -> usize {
         self.buffer.cursor()
     }
+
+    fn selection(&self) -> Option<&[char]> {
+        self.buffer.selection().map(|range| &self.buffer[range])
+    }
 }
 
 impl<'a> ContextImpl<'a> {
     pub(super) fn new(
         erase_on_drop: bool,
-        prompt: Option<&CharString>,
-        completer: &'a Option<Box<dyn Completer>>,
-        suggester: &'a Option<Box<dyn Suggester>>,
+        prompt: Option<&'a CharString>,
+        right_prompt: Option<&'a CharString>,
+        keymap: Keymap,
+        words: WordBoundary,
+        completer: Option<&'a dyn Completer>,
+        suggester: Option<&'a dyn Suggester>,
     ) -> Result<Self, crate::ErrorKind> {
+        let vi = match keymap {
+            Keymap::Vi => Some(Vi::new(words.clone())),
+            Keymap::Rucline | Keymap::Emacs | Keymap::Minimal => None,
+        };
+        let indicator = vi.as_ref().map(|vi| vi.mode().indicator());
+
         Ok(Self {
-            writer: Writer::new(erase_on_drop, prompt)?,
+            writer: Writer::new(erase_on_drop, prompt, right_prompt, indicator)?,
             buffer: Buffer::new(),
             completer,
             completion: None,
             suggester,
             suggestions: None,
+            filter_menu: false,
+            clipboard: &Osc52,
+            vi,
+            killed: Vec::new(),
+            words,
         })
     }
 
@@ -48,21 +73,181 @@ impl<'a> ContextImpl<'a> {
     }
 
     pub(super) fn print(&mut self) -> Result<(), crate::ErrorKind> {
-        self.writer.print(&self.buffer, self.completion)
+        self.writer.print(&self.buffer, self.completion.as_deref())
     }
 
     pub(super) fn write(&mut self, c: char) -> Result<(), crate::ErrorKind> {
-        self.try_take_suggestion();
+        self.take_suggestion_unless_filtering();
+        self.buffer.take_selection();
         self.buffer.write(c);
+        self.print_edit()
+    }
+
+    pub(super) fn paste(&mut self, text: &str) -> Result<(), crate::ErrorKind> {
+        self.try_take_suggestion();
+        self.buffer.take_selection();
+        self.buffer.write_str(&text.chars().collect::<Vec<_>>());
         self.update_completion();
-        self.writer.print(&self.buffer, self.completion)
+        self.writer.print(&self.buffer, self.completion.as_deref())
     }
 
     pub(super) fn delete(&mut self, scope: Scope) -> Result<(), crate::ErrorKind> {
+        self.take_suggestion_unless_filtering();
+
+        // Deleting a single character with a selected region deletes the region instead
+        if let Scope::Relative(Range::Single, _) = scope {
+            if self.buffer.take_selection().is_some() {
+                return self.print_edit();
+            }
+        }
+
+        let deleted = self.buffer.delete(scope, &self.words);
+
+        // Only words and lines are kept to be yanked back, as readline does
+        if !matches!(scope, Scope::Relative(Range::Single, _)) && !deleted.is_empty() {
+            self.killed = deleted;
+        }
+
+        self.print_edit()
+    }
+
+    /// Replaces the buffer with the selected suggestion before an edit, unless the edit narrows
+    /// the suggestions instead.
+    fn take_suggestion_unless_filtering(&mut self) {
+        if !self.is_filtering() {
+            self.try_take_suggestion();
+        }
+    }
+
+    /// Prints the buffer after an edit, narrowing the suggestions if they are being filtered.
+    fn print_edit(&mut self) -> Result<(), crate::ErrorKind> {
+        if self.is_filtering() {
+            self.filter_suggestions()
+        } else {
+            self.update_completion();
+            self.writer.print(&self.buffer, self.completion.as_deref())
+        }
+    }
+
+    pub(super) fn move_to_char(
+        &mut self,
+        search: CharSearch,
+        target: char,
+    ) -> Result<(), crate::ErrorKind> {
+        self.try_take_suggestion();
+        self.buffer.move_to_char(search, target);
+        self.writer.print(&self.buffer, self.completion.as_deref())
+    }
+
+    pub(super) fn delete_to_char(
+        &mut self,
+        search: CharSearch,
+        target: char,
+    ) -> Result<(), crate::ErrorKind> {
         self.try_take_suggestion();
-        self.buffer.delete(scope);
+        let deleted = self.buffer.delete_to_char(search, target);
+        if !deleted.is_empty() {
+            self.killed = deleted;
+        }
         self.update_completion();
-        self.writer.print(&self.buffer, self.completion)
+        self.writer.print(&self.buffer, self.completion.as_deref())
+    }
+
+    pub(super) fn yank(&mut self) -> Result<(), crate::ErrorKind> {
+        self.try_take_suggestion();
+        self.buffer.take_selection();
+        self.buffer.write_str(&self.killed);
+        self.update_completion();
+        self.writer.print(&self.buffer, self.completion.as_deref())
+    }
+
+    pub(super) fn transpose_chars(&mut self) -> Result<(), crate::ErrorKind> {
+        self.try_take_suggestion();
+        self.buffer.transpose_chars();
+        self.update_completion();
+        self.writer.print(&self.buffer, self.completion.as_deref())
+    }
+
+    pub(super) fn transpose_words(&mut self) -> Result<(), crate::ErrorKind> {
+        self.try_take_suggestion();
+        self.buffer.transpose_words(&self.words);
+        self.update_completion();
+        self.writer.print(&self.buffer, self.completion.as_deref())
+    }
+
+    pub(super) fn change_case(&mut self, case: Case) -> Result<(), crate::ErrorKind> {
+        self.try_take_suggestion();
+        self.buffer.change_case(case, &self.words);
+        self.update_completion();
+        self.writer.print(&self.buffer, self.completion.as_deref())
+    }
+
+    pub(super) fn set_mark(&mut self) -> Result<(), crate::ErrorKind> {
+        self.try_take_suggestion();
+        self.buffer.set_mark();
+        self.writer.print(&self.buffer, self.completion.as_deref())
+    }
+
+    pub(super) fn select(
+        &mut self,
+        range: Range,
+        direction: Direction,
+    ) -> Result<(), crate::ErrorKind> {
+        self.try_take_suggestion();
+        self.buffer.select(range, direction, &self.words);
+        self.writer.print(&self.buffer, self.completion.as_deref())
+    }
+
+    pub(super) fn copy_region(&mut self) -> Result<(), crate::ErrorKind> {
+        self.try_take_suggestion();
+        if let Some(range) = self.buffer.selection() {
+            self.killed = self.buffer[range].to_vec();
+        }
+        self.buffer.clear_mark();
+        self.writer.print(&self.buffer, self.completion.as_deref())
+    }
+
+    pub(super) fn cut_region(&mut self) -> Result<(), crate::ErrorKind> {
+        self.try_take_suggestion();
+        if let Some(cut) = self.buffer.take_selection() {
+            self.killed = cut;
+        }
+        self.update_completion();
+        self.writer.print(&self.buffer, self.completion.as_deref())
+    }
+
+    /// Opens the buffer in an external editor, replacing it with the edited text.
+    pub(super) fn open_editor(&mut self) -> Result<(), crate::ErrorKind> {
+        self.try_take_suggestion();
+
+        self.writer.suspend()?;
+        let edited = editor::edit(&self.buffer.to_string());
+        self.writer
+            .resume(self.vi.as_ref().map(|vi| vi.mode().indicator()))?;
+
+        if let Some(text) = edited? {
+            self.buffer = Buffer::from(text.chars().collect::<Vec<_>>().as_slice());
+        }
+
+        self.update_completion();
+        self.writer.print(&self.buffer, self.completion.as_deref())
+    }
+
+    /// Controls if editing while the suggestions are shown narrows them instead of closing them.
+    pub(super) fn set_filter_menu(&mut self, filter_menu: bool) {
+        self.filter_menu = filter_menu;
+    }
+
+    pub(super) fn set_clipboard(&mut self, clipboard: &'a dyn Clipboard) {
+        self.clipboard = clipboard;
+    }
+
+    pub(super) fn copy_to_clipboard(&mut self) -> Result<(), crate::ErrorKind> {
+        let text = match self.buffer.selection() {
+            Some(range) => self.buffer[range].iter().collect::<String>(),
+            None => self.buffer.to_string(),
+        };
+        self.clipboard.copy(&text)
     }
 
     pub(super) fn move_cursor(
@@ -71,8 +256,8 @@ impl<'a> ContextImpl<'a> {
         direction: Direction,
     ) -> Result<(), crate::ErrorKind> {
         self.try_take_suggestion();
-        self.buffer.move_cursor(range, direction);
-        self.writer.print(&self.buffer, self.completion)
+        self.buffer.move_cursor(range, direction, &self.words);
+        self.writer.print(&self.buffer, self.completion.as_deref())
     }
 
     pub(super) fn complete(&mut self, range: Range) -> Result<(), crate::ErrorKind> {
@@ -82,18 +267,20 @@ impl<'a> ContextImpl<'a> {
                 Range::Line => {
                     self.buffer.write_str(completion);
                     self.update_completion();
-                    self.writer.print(&self.buffer, self.completion)
+                    self.writer.print(&self.buffer, self.completion.as_deref())
                 }
                 Range::Word => {
-                    let index = navigation::next_word(0, &completion);
+                    let index = navigation::next_word(0, completion, &self.words);
                     self.buffer.write_str(&completion[0..index]);
                     self.update_completion();
-                    self.writer.print(&self.buffer, self.completion)
+                    self.writer.print(&self.buffer, self.completion.as_deref())
                 }
                 Range::Single => {
-                    self.buffer.write(completion[0]);
+                    if let Some(&c) = completion.first() {
+                        self.buffer.write(c);
+                    }
                     self.update_completion();
-                    self.writer.print(&self.buffer, self.completion)
+                    self.writer.print(&self.buffer, self.completion.as_deref())
                 }
             }
         } else {
@@ -101,60 +288,200 @@ impl<'a> ContextImpl<'a> {
         }
     }
 
+    /// Lets the vi keymap, if any, handle the `event`.
+    ///
+    /// Returns `false` if the event was not claimed and should be mapped to an action instead.
+    pub(super) fn handle_vi(&mut self, event: Event) -> Result<bool, crate::ErrorKind> {
+        use crossterm::event::KeyCode::Esc;
+
+        let claimed = self.vi.as_ref().is_some_and(|vi| vi.claims(event));
+
+        // Escape should close the suggestions before switching modes
+        if !claimed || (self.is_suggesting() && event.code == Esc) {
+            return Ok(false);
+        }
+
+        self.try_take_suggestion();
+        if let Some(vi) = &mut self.vi {
+            let mode = vi.mode();
+            vi.handle(event, &mut self.buffer);
+            if vi.mode() != mode {
+                self.writer.print_indicator(vi.mode().indicator())?;
+            }
+        }
+
+        self.update_completion();
+        self.writer
+            .print(&self.buffer, self.completion.as_deref())?;
+        Ok(true)
+    }
+
+    /// Lets the open suggestion menu, if any, handle the `event`.
+    ///
+    /// The arrows move the selection around the menu and `PageUp` and `PageDown` move it a page
+    /// at a time. Left and right are only claimed when the menu has several columns, so that
+    /// they still move the cursor otherwise.
+    ///
+    /// Returns `false` if the event was not claimed and should be mapped to an action instead.
+    pub(super) fn handle_menu(&mut self, event: Event) -> Result<bool, crate::ErrorKind> {
+        use crossterm::event::KeyModifiers;
+
+        let grid = match (&self.suggestions, self.writer.grid()) {
+            (Some(suggestions), Some(grid)) if suggestions.index.is_some() => grid,
+            _ => return Ok(false),
+        };
+        if event.modifiers != KeyModifiers::NONE {
+            return Ok(false);
+        }
+
+        if let Some(suggestions) = &mut self.suggestions {
+            if suggestions.navigate(event.code, grid.columns, grid.page_rows) {
+                if let Some(index) = suggestions.index {
+                    self.writer
+                        .print_suggestions(&self.buffer, index, &suggestions.options)?;
+                }
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    pub(super) fn capture_mouse(&mut self) -> Result<(), crate::ErrorKind> {
+        self.writer.capture_mouse()
+    }
+
+    pub(super) fn bracket_pastes(&mut self) -> Result<(), crate::ErrorKind> {
+        self.writer.bracket_pastes()
+    }
+
+    /// Moves the cursor to a click on the buffer, selects a clicked suggestion and cycles the
+    /// suggestions with the scroll wheel.
+    pub(super) fn handle_mouse(
+        &mut self,
+        event: crossterm::event::MouseEvent,
+    ) -> Result<(), crate::ErrorKind> {
+        use crossterm::event::{MouseButton, MouseEvent};
+
+        match event {
+            MouseEvent::Down(MouseButton::Left, column, row, _) => {
+                match self.writer.drawn_at(column, row)? {
+                    Some(Drawn::Buffer(index)) => {
+                        self.try_take_suggestion();
+                        self.buffer.set_cursor(index);
+                        self.writer.print(&self.buffer, self.completion.as_deref())
+                    }
+                    Some(Drawn::Suggestion(index)) => {
+                        if let Some(suggestions) = &mut self.suggestions {
+                            suggestions.index = Some(index);
+                            self.writer
+                                .print_suggestions(&self.buffer, index, &suggestions.options)
+                        } else {
+                            Ok(())
+                        }
+                    }
+                    None => Ok(()),
+                }
+            }
+            MouseEvent::ScrollDown(..) => self.suggest(Direction::Forward),
+            MouseEvent::ScrollUp(..) => self.suggest(Direction::Backward),
+            _ => Ok(()),
+        }
+    }
+
     fn update_completion(&mut self) {
         if let Some(completer) = self.completer {
-            self.completion = completer.complete_for(self).map(std::convert::Into::into);
+            self.completion = completer.completion_for(self);
         }
     }
 
     pub(super) fn suggest(&mut self, direction: Direction) -> Result<(), crate::ErrorKind> {
-        if let Some(suggester) = &self.suggester {
+        if let Some(suggester) = self.suggester {
             if let Some(suggestions) = &mut self.suggestions {
                 suggestions.cycle(direction);
                 if let Some(index) = suggestions.index {
-                    return self.writer.print_suggestions(index, &suggestions.options);
+                    return self.writer.print_suggestions(
+                        &self.buffer,
+                        index,
+                        &suggestions.options,
+                    );
                 }
             } else {
-                let options = suggester.suggest_for(self);
+                let options = suggester.candidates_for(self);
                 if !options.is_empty() {
-                    self.suggestions = Some(Suggestions::new(
-                        options.into_iter().map(std::convert::Into::into).collect(),
-                        direction,
-                    ));
+                    self.suggestions = Some(Suggestions::new(options, direction));
                     let suggestions = self.suggestions.as_ref().unwrap();
-                    return self
-                        .writer
-                        .print_suggestions(suggestions.index.unwrap(), &suggestions.options);
+                    return self.writer.print_suggestions(
+                        &self.buffer,
+                        suggestions.index.unwrap(),
+                        &suggestions.options,
+                    );
                 }
             }
         }
 
-        self.writer.print(&self.buffer, self.completion)
+        self.writer.print(&self.buffer, self.completion.as_deref())
     }
 
     pub(super) fn is_suggesting(&self) -> bool {
         self.suggestions.is_some()
     }
 
+    /// Whether edits are narrowing the open suggestions.
+    pub(super) fn is_filtering(&self) -> bool {
+        self.filter_menu && self.suggestions.is_some()
+    }
+
+    /// Replaces the buffer with the selected suggestion, if any, and closes the suggestions.
+    pub(super) fn take_suggestion(&mut self) -> Result<(), crate::ErrorKind> {
+        self.try_take_suggestion();
+        self.update_completion();
+        self.writer.print(&self.buffer, self.completion.as_deref())
+    }
+
+    /// Asks the suggester again for the edited buffer and narrows its answer down to what was
+    /// typed, selecting the first suggestion.
+    ///
+    /// The suggestions stay open even if there are none left, so that deleting what was typed
+    /// brings them back.
+    fn filter_suggestions(&mut self) -> Result<(), crate::ErrorKind> {
+        self.update_completion();
+
+        let options = self
+            .suggester
+            .map(|suggester| narrow(suggester.candidates_for(self), self))
+            .unwrap_or_default();
+        let suggestions = self.suggestions.insert(Suggestions::narrowed(options));
+
+        match suggestions.index {
+            Some(index) => self
+                .writer
+                .print_suggestions(&self.buffer, index, &suggestions.options),
+            None => self.writer.print(&self.buffer, self.completion.as_deref()),
+        }
+    }
+
     pub(super) fn cancel_suggestion(&mut self) -> Result<(), crate::ErrorKind> {
         self.suggestions = None;
-        self.writer.print(&self.buffer, self.completion)
+        self.writer.print(&self.buffer, self.completion.as_deref())
     }
 
     fn try_take_suggestion(&mut self) {
-        if let Some(suggestion) = self.suggestions.take().and_then(Suggestions::take) {
-            self.buffer = suggestion;
+        if let Some(candidate) = self.suggestions.take().and_then(Suggestions::take) {
+            let (buffer, cursor) = candidate.apply(&self.buffer);
+            self.buffer = Buffer::from(&buffer[..]);
+            self.buffer.set_cursor(cursor);
         }
     }
 }
 
 struct Suggestions<'a> {
     index: Option<usize>,
-    options: Vec<CharStringView<'a>>,
+    options: Vec<Candidate<'a>>,
 }
 
 impl<'a> Suggestions<'a> {
-    fn new(options: Vec<CharStringView<'a>>, direction: Direction) -> Self {
+    fn new(options: Vec<Candidate<'a>>, direction: Direction) -> Self {
         let index = match direction {
             Direction::Forward => 0,
             Direction::Backward => options.len() - 1,
@@ -166,11 +493,24 @@ impl<'a> Suggestions<'a> {
         }
     }
 
+    /// The `options` left after narrowing, with the first one selected, if any.
+    fn narrowed(options: Vec<Candidate<'a>>) -> Self {
+        Self {
+            index: if options.is_empty() { None } else { Some(0) },
+            options,
+        }
+    }
+
     // Allowed because it is more readable
     #[allow(clippy::match_same_arms)]
     fn cycle(&mut self, direction: Direction) {
         use Direction::{Backward, Forward};
 
+        // Filtering may leave nothing to cycle through
+        if self.options.is_empty() {
+            return;
+        }
+
         let last_index = self.options.len() - 1;
 
         self.index = match (direction, self.index) {
@@ -183,11 +523,194 @@ impl<'a> Suggestions<'a> {
         };
     }
 
-    fn take(mut self) -> Option<Buffer> {
-        if let Some(index) = self.index {
-            Some(Buffer::from(self.options.swap_remove(index).as_slice()))
-        } else {
-            None
-        }
+    /// Moves the selection around a menu laid out in `columns`, of which `page_rows` rows are
+    /// shown at a time.
+    ///
+    /// Returns `false` if the `code` does not move around the menu.
+    fn navigate(
+        &mut self,
+        code: crossterm::event::KeyCode,
+        columns: usize,
+        page_rows: usize,
+    ) -> bool {
+        use crossterm::event::KeyCode::{Down, Left, PageDown, PageUp, Right, Up};
+
+        let Some(index) = self.index else {
+            return false;
+        };
+        let last_index = self.options.len() - 1;
+        let page = columns * page_rows;
+
+        self.index = Some(match code {
+            Left if columns > 1 => index.saturating_sub(1),
+            Right if columns > 1 => (index + 1).min(last_index),
+            Up => index.checked_sub(columns).unwrap_or(index),
+            // Moving down from above a short last row lands on its last candidate
+            Down if index + columns <= last_index => index + columns,
+            Down if index / columns < last_index / columns => last_index,
+            Down => index,
+            PageUp => index.saturating_sub(page),
+            PageDown => (index + page).min(last_index),
+            _ => return false,
+        });
+        true
+    }
+
+    fn take(mut self) -> Option<Candidate<'a>> {
+        self.index.map(|index| self.options.swap_remove(index))
+    }
+}
+
+/// Keeps the `candidates` whose label starts with what was typed in their span, up to the
+/// cursor, ignoring case.
+///
+/// Candidates with highlights were matched by the suggester itself, so they are kept as they are.
+fn narrow<'a>(candidates: Vec<Candidate<'a>>, context: &dyn Context) -> Vec<Candidate<'a>> {
+    let buffer = context.buffer();
+    let cursor = context.cursor().min(buffer.len());
+
+    candidates
+        .into_iter()
+        .filter(|candidate| {
+            if !candidate.highlights().is_empty() {
+                return true;
+            }
+
+            let end = candidate.span().end.min(cursor);
+            let typed = &buffer[candidate.span().start.min(end)..end];
+            let label = candidate.label();
+            typed.len() <= label.len()
+                && typed
+                    .iter()
+                    .zip(label)
+                    .all(|(typed, label)| typed.to_lowercase().eq(label.to_lowercase()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{narrow, Candidate, Direction, Suggestions};
+    use crate::completion::{Basic, Suggester};
+    use crate::test::mock::Context as Mock;
+    use crossterm::event::KeyCode::{Down, Left, PageDown, PageUp, Right, Tab, Up};
+
+    fn suggestions(count: usize) -> Suggestions<'static> {
+        let options = (0..count)
+            .map(|_| Candidate::new(vec!['x'], 0..0))
+            .collect();
+        Suggestions::new(options, Direction::Forward)
+    }
+
+    /// The suggestions left open by filtering the `buffer`.
+    fn filter<'a>(suggester: &'a dyn Suggester, buffer: &str) -> Suggestions<'a> {
+        let context = Mock::from(buffer);
+        Suggestions::narrowed(narrow(suggester.candidates_for(&context), &context))
+    }
+
+    fn labels(suggestions: &Suggestions<'_>) -> Vec<String> {
+        suggestions
+            .options
+            .iter()
+            .map(|candidate| candidate.label().iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn writing_narrows() {
+        // The suggester does not filter on its own
+        let basic = Basic::new(&["checkout", "cherry-pick", "commit"]);
+
+        assert_eq!(labels(&filter(&basic, "c")).len(), 3);
+        assert_eq!(
+            labels(&filter(&basic, "ch")),
+            vec!["checkout", "cherry-pick"]
+        );
+        assert_eq!(labels(&filter(&basic, "CHER")), vec!["cherry-pick"]);
+
+        let nothing = filter(&basic, "x");
+        assert!(nothing.options.is_empty());
+        assert_eq!(nothing.index, None);
+    }
+
+    #[test]
+    fn deleting_widens() {
+        let basic = Basic::new(&["checkout", "cherry-pick", "commit"]);
+
+        let typed = filter(&basic, "cher");
+        assert_eq!(labels(&typed), vec!["cherry-pick"]);
+
+        let deleted = filter(&basic, "che");
+        assert_eq!(labels(&deleted), vec!["checkout", "cherry-pick"]);
+        assert_eq!(deleted.index, Some(0));
+    }
+
+    #[test]
+    fn accepting_picks_the_selected() {
+        let basic = Basic::new(&["checkout", "cherry-pick", "commit"]);
+        let context = Mock::from("ch");
+
+        let mut menu = filter(&basic, "ch");
+        assert!(menu.navigate(Down, 1, 2));
+
+        let (buffer, cursor) = menu.take().unwrap().apply(&context.buffer);
+        assert_eq!(buffer.iter().collect::<String>(), "cherry-pick");
+        assert_eq!(cursor, 11);
+    }
+
+    #[test]
+    fn keep_highlighted_candidates() {
+        let fuzzy = crate::completion::Fuzzy::new(&["checkout", "cherry-pick", "commit"]);
+        assert_eq!(labels(&filter(&fuzzy, "cp")), vec!["cherry-pick"]);
+    }
+
+    #[test]
+    fn navigate_grid() {
+        // Seven candidates in three columns:
+        // 0 1 2
+        // 3 4 5
+        // 6
+        let mut menu = suggestions(7);
+        let mut go = |code| {
+            assert!(menu.navigate(code, 3, 2));
+            menu.index.unwrap()
+        };
+
+        assert_eq!(go(Left), 0);
+        assert_eq!(go(Up), 0);
+        assert_eq!(go(Right), 1);
+        assert_eq!(go(Down), 4);
+        assert_eq!(go(Down), 6);
+        assert_eq!(go(Down), 6);
+        assert_eq!(go(Right), 6);
+        assert_eq!(go(Up), 3);
+        assert_eq!(go(Left), 2);
+        assert_eq!(go(PageDown), 6);
+        assert_eq!(go(PageUp), 0);
+    }
+
+    #[test]
+    fn cycle_nothing() {
+        let mut menu = suggestions(1);
+        menu.options.clear();
+        menu.index = None;
+
+        menu.cycle(Direction::Forward);
+        assert_eq!(menu.index, None);
+        menu.cycle(Direction::Backward);
+        assert_eq!(menu.index, None);
+    }
+
+    #[test]
+    fn leave_left_and_right_to_single_columns() {
+        let mut menu = suggestions(3);
+        assert!(!menu.navigate(Left, 1, 3));
+        assert!(!menu.navigate(Right, 1, 3));
+        assert!(!menu.navigate(Tab, 1, 3));
+        assert!(menu.navigate(Down, 1, 3));
+        assert_eq!(menu.index, Some(1));
+
+        menu.index = None;
+        assert!(!menu.navigate(Down, 1, 3));
     }
 }
@@ -0,0 +1,107 @@
+/// Opens `text` in the editor named by `$VISUAL` or `$EDITOR`, falling back to `vi`, and
+/// returns the edited text.
+///
+/// Returns `None` if the editor exits with an error, in which case the edit should be
+/// discarded.
+pub(super) fn edit(text: &str) -> Result<Option<String>, crate::ErrorKind> {
+    let command = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|variable| std::env::var(variable).ok())
+        .find(|command| !command.trim().is_empty())
+        .unwrap_or_else(|| String::from("vi"));
+
+    edit_with(&command, text)
+}
+
+/// Opens `text` with `command`, which may include arguments to pass before the file name.
+///
+/// The command is split into words as a shell would, so that quotes and escapes keep paths with
+/// spaces together.
+fn edit_with(command: &str, text: &str) -> Result<Option<String>, crate::ErrorKind> {
+    let file = TempFile::create(text)?;
+
+    let words = crate::context::tokenize(&command.chars().collect::<Vec<_>>())
+        .iter()
+        .map(|token| token.text().iter().collect::<String>())
+        .collect::<Vec<_>>();
+    let (program, arguments) = words
+        .split_first()
+        .map_or(("vi", &[][..]), |(program, arguments)| {
+            (program.as_str(), arguments)
+        });
+    let status = std::process::Command::new(program)
+        .args(arguments)
+        .arg(&file.path)
+        .status()?;
+
+    if !status.success() {
+        return Ok(None);
+    }
+
+    // Editors usually end the file with a line break, which is not part of the line
+    let edited = std::fs::read_to_string(&file.path)?;
+    Ok(Some(String::from(edited.trim_end_matches(['\n', '\r']))))
+}
+
+/// A file in the temporary directory that is removed when dropped.
+struct TempFile {
+    path: std::path::PathBuf,
+}
+
+impl TempFile {
+    fn create(contents: &str) -> Result<Self, std::io::Error> {
+        use std::io::Write;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        let path = std::env::temp_dir().join(format!(
+            "rucline-{}-{}.txt",
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        // Refuse to follow anything already in place, since the directory is shared
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)?
+            .write_all(contents.as_bytes())?;
+
+        Ok(Self { path })
+    }
+}
+
+impl std::ops::Drop for TempFile {
+    // Allowed because there is nothing left to do if the file cannot be removed
+    #[allow(unused_must_use)]
+    fn drop(&mut self) {
+        std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::edit_with;
+
+    #[test]
+    fn read_back_edited_text() {
+        assert_eq!(
+            edit_with("sed -i s/world/there/", "hello world").unwrap(),
+            Some(String::from("hello there"))
+        );
+    }
+
+    #[test]
+    fn split_command_as_a_shell() {
+        assert_eq!(
+            edit_with(r#"'sed' -i "s/world/big  world/""#, "hello world").unwrap(),
+            Some(String::from("hello big  world"))
+        );
+    }
+
+    #[test]
+    fn discard_failed_edits() {
+        assert_eq!(edit_with("false", "hello").unwrap(), None);
+    }
+}
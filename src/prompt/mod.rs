@@ -45,16 +45,26 @@
 mod buffer;
 mod char_string;
 mod context;
+mod editor;
 mod navigation;
+mod vi;
 mod writer;
 
 use buffer::Buffer;
 use char_string::{CharString, CharStringView};
 use context::ContextImpl;
-use writer::Writer;
+use vi::Vi;
+use writer::{Drawn, Writer};
 
-use crate::actions::{action_for, Action, Direction, Overrider, Range, Scope};
-use crate::completion::{Completer, Suggester};
+use crate::actions::{
+    action_for, keymap_chords, paste_action, Action, Case, CharSearch, Direction, Event, Keymap,
+    Overrider, PastedNewlines, Range, Scope, WordBoundary,
+};
+use crate::clipboard::Clipboard;
+use crate::completion::{Candidate, Completer, Suggester};
+
+/// The longest pause between two chunks of the same paste.
+const PASTE_GAP: std::time::Duration = std::time::Duration::from_millis(10);
 
 /// Represents and stores a prompt that shall be presented to the user for input.
 ///
@@ -62,12 +72,23 @@ use crate::completion::{Completer, Suggester};
 /// [`erase_after_read`] is `true`.
 ///
 /// [`erase_after_read`]: struct.Prompt.html#method.erase_after_read
+#[derive(Default)]
+// Allowed because each flag is an independent option of the builder
+#[allow(clippy::struct_excessive_bools)]
 pub struct Prompt {
     erase_after_read: bool,
     text: Option<CharString>,
+    right_text: Option<CharString>,
+    keymap: Keymap,
+    word_boundary: WordBoundary,
+    pasted_newlines: PastedNewlines,
+    detect_pastes: bool,
+    capture_mouse: bool,
+    filter_suggestions: bool,
     overrider: Option<Box<dyn Overrider>>,
     completer: Option<Box<dyn Completer>>,
     suggester: Option<Box<dyn Suggester>>,
+    clipboard: Option<Box<dyn Clipboard>>,
 }
 
 impl Prompt {
@@ -97,6 +118,30 @@ impl Prompt {
         self
     }
 
+    /// Modifies the right prompt text
+    ///
+    /// The right prompt is rendered flush right on the line of the buffer, similar to the
+    /// `RPROMPT` of `zsh`. It is hidden whenever the buffer grows to collide with it, and it is
+    /// removed once the input is accepted.
+    ///
+    /// # Arguments
+    ///
+    /// * `string` - The new right prompt text
+    #[must_use]
+    // Allowed because `impl ToString` doesn't necessarily need to consume `string`
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn right_text(mut self, string: impl ToString) -> Self {
+        self.right_text = Some(string.to_string().into());
+        self
+    }
+
+    /// Removes the current right prompt text, leaving it empty;
+    #[must_use]
+    pub fn remove_right_text(mut self) -> Self {
+        self.right_text = None;
+        self
+    }
+
     /// Controls if the prompt shall be erased after user input.
     ///
     /// If set to `false` (default), after user input, the terminal will receive a new line
@@ -111,6 +156,109 @@ impl Prompt {
         self
     }
 
+    /// Sets the [`Keymap`] that defines the behavior of the prompt in the absence of an
+    /// override. The default is [`Keymap::Rucline`].
+    ///
+    /// # Arguments
+    ///
+    /// * [`keymap`] - The new keymap
+    ///
+    /// [`Keymap`]: ../actions/enum.Keymap.html
+    /// [`Keymap::Rucline`]: ../actions/enum.Keymap.html#variant.Rucline
+    #[must_use]
+    pub fn keymap(mut self, keymap: Keymap) -> Self {
+        self.keymap = keymap;
+        self
+    }
+
+    /// Sets the [`WordBoundary`] that delimits words for every action on a word, such as
+    /// moving, deleting or completing. The default is [`WordBoundary::Whitespace`].
+    ///
+    /// # Arguments
+    ///
+    /// * `word_boundary` - The new word boundary
+    ///
+    /// [`WordBoundary`]: ../actions/enum.WordBoundary.html
+    /// [`WordBoundary::Whitespace`]: ../actions/enum.WordBoundary.html#variant.Whitespace
+    #[must_use]
+    pub fn word_boundary(mut self, word_boundary: WordBoundary) -> Self {
+        self.word_boundary = word_boundary;
+        self
+    }
+
+    /// Sets how line breaks are handled when text is pasted into the prompt, if pastes are
+    /// detected at all with [`detect_pastes`]. The default is [`PastedNewlines::Keep`].
+    ///
+    /// Pasted text is inserted as is, so a line break in it does not accept the line. The
+    /// [`Overrider`] may still take over pasted text with [`override_paste`].
+    ///
+    /// # Arguments
+    ///
+    /// * `pasted_newlines` - The new policy for line breaks in pasted text
+    ///
+    /// [`detect_pastes`]: #method.detect_pastes
+    /// [`PastedNewlines::Keep`]: ../actions/enum.PastedNewlines.html#variant.Keep
+    /// [`Overrider`]: ../actions/trait.Overrider.html
+    /// [`override_paste`]: ../actions/trait.Overrider.html#method.override_paste
+    #[must_use]
+    pub fn pasted_newlines(mut self, pasted_newlines: PastedNewlines) -> Self {
+        self.pasted_newlines = pasted_newlines;
+        self
+    }
+
+    /// Controls if the prompt guesses which keys were pasted rather than typed. The default is
+    /// `false`, so every key is handled as typed.
+    ///
+    /// The terminal does not report where a paste starts and ends, so this is a guess: keys
+    /// that arrive in a single burst are taken as pasted and inserted as text, following
+    /// [`pasted_newlines`]. Any other key in the burst, such as an arrow, is still handled on its
+    /// own, but a line break never accepts the line, and the text skips the vi normal mode, the
+    /// suggestions and the [`Overrider`], other than its [`override_paste`]. Keys typed ahead of
+    /// the prompt, sent by a script or delayed by a slow connection may arrive bunched together
+    /// and be taken as pasted too.
+    ///
+    /// [`pasted_newlines`]: #method.pasted_newlines
+    /// [`Overrider`]: ../actions/trait.Overrider.html
+    /// [`override_paste`]: ../actions/trait.Overrider.html#method.override_paste
+    #[must_use]
+    pub fn detect_pastes(mut self, detect_pastes: bool) -> Self {
+        self.detect_pastes = detect_pastes;
+        self
+    }
+
+    /// Controls if the prompt captures the mouse while reading a line. The default is `false`.
+    ///
+    /// When capturing, clicking on the buffer moves the cursor there, clicking on a suggestion
+    /// selects it, and the scroll wheel cycles through the suggestions. The terminal is then no
+    /// longer able to select text with the mouse for the duration of the prompt.
+    #[must_use]
+    pub fn capture_mouse(mut self, capture_mouse: bool) -> Self {
+        self.capture_mouse = capture_mouse;
+        self
+    }
+
+    /// Controls if typing while the suggestions are shown narrows them instead of accepting the
+    /// selected one. The default is `false`.
+    ///
+    /// When filtering, every [`Write`] and [`Delete`] edits the buffer behind the suggestions and
+    /// asks the [`Suggester`] again, keeping only the suggestions whose label starts with what
+    /// was typed in their span, ignoring case. So typing narrows the list and deleting widens
+    /// it, whether or not the suggester filters on its own. Suggestions that come with
+    /// highlights were matched by the suggester itself and are kept as they are. [`Accept`]
+    /// then replaces the buffer with the selected suggestion rather than accepting the line,
+    /// and [`Cancel`] closes the suggestions, keeping what was typed.
+    ///
+    /// [`Accept`]: ../actions/enum.Action.html#variant.Accept
+    /// [`Cancel`]: ../actions/enum.Action.html#variant.Cancel
+    /// [`Delete`]: ../actions/enum.Action.html#variant.Delete
+    /// [`Suggester`]: ../completion/trait.Suggester.html
+    /// [`Write`]: ../actions/enum.Action.html#variant.Write
+    #[must_use]
+    pub fn filter_suggestions(mut self, filter_suggestions: bool) -> Self {
+        self.filter_suggestions = filter_suggestions;
+        self
+    }
+
     /// Modifies the behavior of the prompt by setting a [`Overrider`].
     ///
     /// # Arguments
@@ -172,6 +320,22 @@ impl Prompt {
         self
     }
 
+    /// Sets the [`Clipboard`] that receives the text of a [`CopyToClipboard`] action. The
+    /// default is [`Osc52`].
+    ///
+    /// # Arguments
+    ///
+    /// * [`clipboard`] - The new clipboard
+    ///
+    /// [`Clipboard`]: ../clipboard/trait.Clipboard.html
+    /// [`CopyToClipboard`]: ../actions/enum.Action.html#variant.CopyToClipboard
+    /// [`Osc52`]: ../clipboard/struct.Osc52.html
+    #[must_use]
+    pub fn clipboard(mut self, clipboard: impl Clipboard + 'static) -> Self {
+        self.clipboard = Some(Box::new(clipboard));
+        self
+    }
+
     // TODO: Support crossterm async
     /// Blocks until an input is committed by the user.
     ///
@@ -182,7 +346,7 @@ impl Prompt {
     /// # Return
     ///
     /// * `Option<String>` - A string containing the user input, or `None` if the user has
-    /// cancelled the input.
+    ///   cancelled the input.
     ///
     /// # Errors
     /// * [`ErrorKind`] - If an error occurred while reading the user input.
@@ -193,44 +357,317 @@ impl Prompt {
         let mut context = ContextImpl::new(
             self.erase_after_read,
             self.text.as_ref(),
-            &self.completer,
-            &self.suggester,
+            self.right_text.as_ref(),
+            self.keymap,
+            self.word_boundary.clone(),
+            self.completer.as_deref(),
+            self.suggester.as_deref(),
         )?;
 
+        let mut keyboard_macro = Macro::default();
+        let mut awaiting = None;
+        let mut pending = std::collections::VecDeque::new();
+        let mut pasting = false;
+        let chords = keymap_chords(self.keymap);
+
+        if self.capture_mouse {
+            context.capture_mouse()?;
+        }
+        if self.detect_pastes {
+            context.bracket_pastes()?;
+        }
+        if let Some(clipboard) = &self.clipboard {
+            context.set_clipboard(clipboard.as_ref());
+        }
+        context.set_filter_menu(self.filter_suggestions);
+
         context.print()?;
         loop {
-            if let crossterm::event::Event::Key(e) = crossterm::event::read()? {
-                match action_for(&self.overrider, e, &context) {
-                    Action::Write(c) => context.write(c)?,
-                    Action::Delete(scope) => context.delete(scope)?,
-                    Action::Move(range, direction) => context.move_cursor(range, direction)?,
-                    Action::Complete(range) => context.complete(range)?,
-                    Action::Suggest(direction) => context.suggest(direction)?,
-                    Action::Noop => continue,
-                    Action::Cancel => {
-                        if context.is_suggesting() {
-                            context.cancel_suggestion()?;
+            let action =
+                if let Some(action) = self.timed_out(&pending, chords.as_ref(), &context)? {
+                    action
+                } else {
+                    // A paste is over once all the events of its burst were handled
+                    let event = if let Some(event) = pending.pop_front() {
+                        event
+                    } else {
+                        pasting = false;
+                        crossterm::event::read()?
+                    };
+
+                    if let crossterm::event::Event::Key(e) = event {
+                        // Any key other than a character cancels a character search
+                        if let Some(search) = awaiting.take() {
+                            if let Some(target) = typed_char(e) {
+                                keyboard_macro.record(&Action::Write(target));
+                                search_char(&mut context, &search, target)?;
+                            }
+                            continue;
+                        }
+
+                        // Text that arrives in a single burst was pasted rather than typed
+                        if self.detect_pastes
+                            && !pasting
+                            && pending.is_empty()
+                            && pasted_char(e).is_some()
+                        {
+                            read_burst(&mut pending)?;
+                            pasting = !pending.is_empty();
+                        }
+
+                        let pasted = if pasting {
+                            pasted_text(e, &mut pending)
+                        } else {
+                            None
+                        };
+
+                        // The overrider gets the first look, even at the keys of an open menu
+                        if let Some(text) = pasted {
+                            paste_action(&self.overrider, self.pasted_newlines, text, &context)
+                        } else if let Some(action) = self
+                            .overrider
+                            .as_ref()
+                            .and_then(|overrider| overrider.override_for(e, &context))
+                        {
+                            action
+                        } else if context.handle_menu(e)? || context.handle_vi(e)? {
+                            continue;
                         } else {
-                            return Ok(None);
+                            action_for(&None, chords.as_ref(), self.keymap, e, &context)
                         }
+                    } else {
+                        if let crossterm::event::Event::Mouse(m) = event {
+                            context.handle_mouse(m)?;
+                        }
+                        continue;
                     }
-                    Action::Accept => return Ok(Some(context.buffer_as_string())),
+                };
+
+            keyboard_macro.record(&action);
+            match perform(&mut context, &mut keyboard_macro, action)? {
+                Flow::Continue => {}
+                Flow::Await(search) => awaiting = Some(search),
+                Flow::Return(result) => return Ok(result),
+            }
+        }
+    }
+
+    /// Waits for the next event for as long as the overrider or the `chords` of the keymap
+    /// ask, returning the action of the first one whose time is up if none arrives in time.
+    ///
+    /// Events that were already read are handled first.
+    fn timed_out(
+        &self,
+        pending: &std::collections::VecDeque<crossterm::event::Event>,
+        chords: Option<&crate::actions::ChordBindings>,
+        context: &ContextImpl<'_>,
+    ) -> Result<Option<Action>, crate::ErrorKind> {
+        let overriders = self
+            .overrider
+            .as_deref()
+            .into_iter()
+            .chain(chords.map(|chords| chords as &dyn Overrider))
+            .collect::<Vec<_>>();
+
+        let timeout = overriders
+            .iter()
+            .filter_map(|overrider| overrider.pending_timeout())
+            .min();
+        match timeout {
+            Some(timeout) if pending.is_empty() && !crossterm::event::poll(timeout)? => {
+                Ok(overriders
+                    .iter()
+                    .filter(|overrider| {
+                        overrider
+                            .pending_timeout()
+                            .is_some_and(|timeout| timeout.is_zero())
+                    })
+                    .find_map(|overrider| overrider.override_timeout(context)))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// What the prompt should do after performing an [`Action`](../actions/enum.Action.html).
+enum Flow {
+    Continue,
+    /// The character search is waiting for the next key as its target
+    Await(Action),
+    Return(Option<String>),
+}
+
+/// The keyboard macro being recorded, if any, and the last one recorded.
+#[derive(Default)]
+struct Macro {
+    recording: Option<Vec<Action>>,
+    recorded: Vec<Action>,
+    playing: bool,
+}
+
+impl Macro {
+    fn record(&mut self, action: &Action) {
+        match action {
+            Action::StartMacro | Action::EndMacro | Action::PlayMacro => {}
+            _ => {
+                if let Some(recording) = &mut self.recording {
+                    recording.push(action.clone());
                 }
             }
         }
     }
 }
 
-impl Default for Prompt {
-    fn default() -> Self {
-        Self {
-            erase_after_read: false,
-            text: None,
-            overrider: None,
-            completer: None,
-            suggester: None,
+fn perform(
+    context: &mut ContextImpl<'_>,
+    keyboard_macro: &mut Macro,
+    action: Action,
+) -> Result<Flow, crate::ErrorKind> {
+    match action {
+        Action::Write(c) => context.write(c)?,
+        Action::Paste(text) => context.paste(&text)?,
+        Action::Delete(scope) => context.delete(scope)?,
+        Action::Move(range, direction) => context.move_cursor(range, direction)?,
+        Action::Complete(range) => context.complete(range)?,
+        Action::Suggest(direction) => context.suggest(direction)?,
+        Action::Yank => context.yank()?,
+        Action::TransposeChars => context.transpose_chars()?,
+        Action::TransposeWords => context.transpose_words()?,
+        Action::ChangeCase(case) => context.change_case(case)?,
+        Action::SetMark => context.set_mark()?,
+        Action::Select(range, direction) => context.select(range, direction)?,
+        Action::CopyRegion => context.copy_region()?,
+        Action::CutRegion => context.cut_region()?,
+        Action::CopyToClipboard => context.copy_to_clipboard()?,
+        Action::OpenEditor => context.open_editor()?,
+        Action::Noop => {}
+        Action::MoveToChar(_) | Action::DeleteToChar(_) => return Ok(Flow::Await(action)),
+        Action::Sequence(actions) => {
+            let mut actions = actions.into_iter().peekable();
+            while let Some(action) = actions.next() {
+                match perform(context, keyboard_macro, action)? {
+                    Flow::Continue => {}
+                    // A character search takes its target from the following write, if any
+                    Flow::Await(search) => match actions.peek() {
+                        Some(Action::Write(target)) => {
+                            search_char(context, &search, *target)?;
+                            actions.next();
+                        }
+                        Some(_) => {}
+                        None => return Ok(Flow::Await(search)),
+                    },
+                    flow @ Flow::Return(_) => return Ok(flow),
+                }
+            }
+        }
+        Action::StartMacro => keyboard_macro.recording = Some(Vec::new()),
+        Action::EndMacro => {
+            if let Some(recording) = keyboard_macro.recording.take() {
+                keyboard_macro.recorded = recording;
+            }
+        }
+        Action::PlayMacro => {
+            // A macro cannot play itself
+            if !keyboard_macro.playing {
+                keyboard_macro.playing = true;
+                let actions = Action::Sequence(keyboard_macro.recorded.clone());
+                let flow = perform(context, keyboard_macro, actions);
+                keyboard_macro.playing = false;
+                return flow;
+            }
+        }
+        Action::Cancel => {
+            if context.is_suggesting() {
+                context.cancel_suggestion()?;
+            } else {
+                return Ok(Flow::Return(None));
+            }
+        }
+        Action::Accept => {
+            if context.is_filtering() {
+                context.take_suggestion()?;
+            } else {
+                return Ok(Flow::Return(Some(context.buffer_as_string())));
+            }
         }
     }
+
+    Ok(Flow::Continue)
+}
+
+fn search_char(
+    context: &mut ContextImpl<'_>,
+    search: &Action,
+    target: char,
+) -> Result<(), crate::ErrorKind> {
+    match search {
+        Action::MoveToChar(search) => context.move_to_char(*search, target),
+        Action::DeleteToChar(search) => context.delete_to_char(*search, target),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the character typed with `event`, if it is a plain key.
+fn typed_char(event: Event) -> Option<char> {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    match event.code {
+        KeyCode::Char(c)
+            if event.modifiers == KeyModifiers::NONE || event.modifiers == KeyModifiers::SHIFT =>
+        {
+            Some(c)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the character `event` inserts when it is part of pasted text.
+fn pasted_char(event: Event) -> Option<char> {
+    use crossterm::event::KeyCode;
+
+    match event.code {
+        KeyCode::Enter => Some('\n'),
+        KeyCode::Tab => Some('\t'),
+        _ => typed_char(event),
+    }
+}
+
+/// Reads the events that arrive right after the one just read, as the rest of a paste would.
+///
+/// Terminals may deliver a long paste in several chunks, so the burst only ends once no event
+/// arrives for [`PASTE_GAP`](constant.PASTE_GAP.html).
+fn read_burst(
+    pending: &mut std::collections::VecDeque<crossterm::event::Event>,
+) -> Result<(), crate::ErrorKind> {
+    if crossterm::event::poll(std::time::Duration::from_millis(0))? {
+        pending.push_back(crossterm::event::read()?);
+        while crossterm::event::poll(PASTE_GAP)? {
+            pending.push_back(crossterm::event::read()?);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the text pasted with `first` and the text events that directly follow it, taking
+/// them out of `following`, or `None` if `first` is not text.
+///
+/// Any other key ends the text, so it can be handled on its own before the rest of the paste.
+fn pasted_text(
+    first: Event,
+    following: &mut std::collections::VecDeque<crossterm::event::Event>,
+) -> Option<String> {
+    let mut text = String::new();
+    text.push(pasted_char(first)?);
+
+    while let Some(c) = following.front().and_then(|event| match event {
+        crossterm::event::Event::Key(e) => pasted_char(*e),
+        _ => None,
+    }) {
+        text.push(c);
+        following.pop_front();
+    }
+
+    Some(text)
 }
 
 impl<S: ToString> std::convert::From<S> for Prompt {
@@ -238,16 +675,24 @@ impl<S: ToString> std::convert::From<S> for Prompt {
         Self {
             erase_after_read: false,
             text: Some(string.to_string().into()),
+            right_text: None,
+            keymap: Keymap::default(),
+            word_boundary: WordBoundary::default(),
+            pasted_newlines: PastedNewlines::default(),
+            detect_pastes: false,
+            capture_mouse: false,
+            filter_suggestions: false,
             overrider: None,
             completer: None,
             suggester: None,
+            clipboard: None,
         }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::Prompt;
+    use super::{pasted_text, typed_char, Action, Macro, Prompt};
 
     #[test]
     fn can_reuse_prompt() {
@@ -258,6 +703,20 @@ mod test {
         assert!(!prompt.erase_after_read);
     }
 
+    #[test]
+    fn detect_pastes() {
+        let prompt = Prompt::from("> ");
+        assert!(!prompt.detect_pastes);
+        assert!(prompt.detect_pastes(true).detect_pastes);
+    }
+
+    #[test]
+    fn filter_suggestions() {
+        let prompt = Prompt::from("> ");
+        assert!(!prompt.filter_suggestions);
+        assert!(prompt.filter_suggestions(true).filter_suggestions);
+    }
+
     #[test]
     fn accept_decorated_prompt() {
         use colored::Colorize;
@@ -286,9 +745,128 @@ mod test {
         match &prompt.text {
             Some(text) => assert_eq!(text.to_string(), "Bla"),
             None => panic!(),
-        };
+        }
 
         prompt = prompt.remove_text();
         assert!(prompt.text.is_none());
     }
+
+    #[test]
+    fn remove_right_text() {
+        let mut prompt = Prompt::from("Bla");
+        assert!(prompt.right_text.is_none());
+
+        prompt = prompt.right_text("Ble");
+        match &prompt.right_text {
+            Some(text) => assert_eq!(text.to_string(), "Ble"),
+            None => panic!(),
+        }
+
+        prompt = prompt.remove_right_text();
+        assert!(prompt.right_text.is_none());
+        assert!(prompt.text.is_some());
+    }
+
+    #[test]
+    fn only_plain_keys_are_search_targets() {
+        use crate::actions::Event;
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        assert_eq!(typed_char(Event::from(KeyCode::Char('/'))), Some('/'));
+        assert_eq!(
+            typed_char(Event::new(KeyCode::Char('A'), KeyModifiers::SHIFT)),
+            Some('A')
+        );
+        assert_eq!(
+            typed_char(Event::new(KeyCode::Char('a'), KeyModifiers::CONTROL)),
+            None
+        );
+        assert_eq!(typed_char(Event::from(KeyCode::Esc)), None);
+    }
+
+    #[test]
+    fn bursts_of_text_are_pasted() {
+        use crate::actions::Event;
+        use crossterm::event::{Event::Key, KeyCode, KeyModifiers};
+
+        let burst = |codes: &[KeyCode]| {
+            codes
+                .iter()
+                .map(|code| Key(Event::from(*code)))
+                .collect::<std::collections::VecDeque<_>>()
+        };
+        let a = Event::from(KeyCode::Char('a'));
+
+        assert_eq!(pasted_text(a, &mut burst(&[])), Some(String::from("a")));
+        let mut following = burst(&[KeyCode::Enter, KeyCode::Tab, KeyCode::Char('b')]);
+        assert_eq!(pasted_text(a, &mut following), Some(String::from("a\n\tb")));
+        assert!(following.is_empty());
+
+        assert_eq!(
+            pasted_text(Event::from(KeyCode::Left), &mut burst(&[])),
+            None
+        );
+        let mut following =
+            std::iter::once(Key(Event::new(KeyCode::Char('b'), KeyModifiers::CONTROL))).collect();
+        assert_eq!(pasted_text(a, &mut following), Some(String::from("a")));
+        assert_eq!(following.len(), 1);
+    }
+
+    #[test]
+    fn enter_in_a_mixed_burst_is_pasted() {
+        use crate::actions::Event;
+        use crossterm::event::{Event::Key, KeyCode};
+
+        // "a", "b", Enter, Esc, Left, "c", Enter
+        let mut burst = [
+            KeyCode::Char('b'),
+            KeyCode::Enter,
+            KeyCode::Esc,
+            KeyCode::Left,
+            KeyCode::Char('c'),
+            KeyCode::Enter,
+        ]
+        .iter()
+        .map(|code| Key(Event::from(*code)))
+        .collect::<std::collections::VecDeque<_>>();
+
+        // As in the prompt, text is pasted and the other keys are handled on their own
+        let mut pasted = Vec::new();
+        let mut handled = Vec::new();
+        let mut next = Some(Key(Event::from(KeyCode::Char('a'))));
+        while let Some(Key(e)) = next {
+            match pasted_text(e, &mut burst) {
+                Some(text) => pasted.push(text),
+                None => handled.push(e.code),
+            }
+            next = burst.pop_front();
+        }
+
+        assert_eq!(pasted, vec![String::from("ab\n"), String::from("c\n")]);
+        assert_eq!(handled, vec![KeyCode::Esc, KeyCode::Left]);
+
+        // An Enter left at the end of a burst is still pasted
+        assert_eq!(
+            pasted_text(Event::from(KeyCode::Enter), &mut burst),
+            Some(String::from("\n"))
+        );
+    }
+
+    #[test]
+    fn record_macro() {
+        let mut keyboard_macro = Macro::default();
+
+        keyboard_macro.record(&Action::Write('a'));
+        assert!(keyboard_macro.recording.is_none());
+
+        keyboard_macro.recording = Some(Vec::new());
+        keyboard_macro.record(&Action::Write('b'));
+        keyboard_macro.record(&Action::PlayMacro);
+        keyboard_macro.record(&Action::Accept);
+        keyboard_macro.record(&Action::EndMacro);
+        assert_eq!(
+            keyboard_macro.recording,
+            Some(vec![Action::Write('b'), Action::Accept])
+        );
+    }
 }
@@ -1,6 +1,14 @@
-// TODO: Add more characters to word separation
+use crate::actions::WordBoundary;
 
-pub(super) fn next_word(pivot: usize, string: &[char]) -> usize {
+/// Returns `true` if the characters at `index - 1` and `index` belong to the same word.
+#[inline]
+fn joined(string: &[char], index: usize, words: &WordBoundary) -> bool {
+    !words.is_separator(string[index - 1])
+        && !words.is_separator(string[index])
+        && !words.splits(string[index - 1], string[index])
+}
+
+pub(super) fn next_word(pivot: usize, string: &[char], words: &WordBoundary) -> usize {
     let end = string.len();
     if pivot == end {
         pivot
@@ -8,12 +16,15 @@ pub(super) fn next_word(pivot: usize, string: &[char]) -> usize {
         let mut index = pivot;
 
         // Go through the characters of the word
-        while index < end && !string[index].is_whitespace() {
+        if !words.is_separator(string[index]) {
             index += 1;
+            while index < end && joined(string, index, words) {
+                index += 1;
+            }
         }
 
-        // Go through the trailing whitespace, if any
-        while index < end && string[index].is_whitespace() {
+        // Go through the trailing separators, if any
+        while index < end && words.is_separator(string[index]) {
             index += 1;
         }
 
@@ -21,19 +32,19 @@ pub(super) fn next_word(pivot: usize, string: &[char]) -> usize {
     }
 }
 
-pub(super) fn previous_word(pivot: usize, string: &[char]) -> usize {
+pub(super) fn previous_word(pivot: usize, string: &[char], words: &WordBoundary) -> usize {
     if pivot == 0 {
         pivot
     } else {
         let mut index = pivot - 1;
 
-        // Go through the trailing whitespace, if any
-        while index > 0 && string[index].is_whitespace() {
+        // Go through the trailing separators, if any
+        while index > 0 && words.is_separator(string[index]) {
             index -= 1;
         }
 
         // Go through the characters of the word
-        while index > 0 && !string[index - 1].is_whitespace() {
+        while index > 0 && joined(string, index, words) {
             index -= 1;
         }
 
@@ -41,26 +52,29 @@ pub(super) fn previous_word(pivot: usize, string: &[char]) -> usize {
     }
 }
 
-pub(super) fn previous_word_end(pivot: usize, string: &[char]) -> usize {
+pub(super) fn previous_word_end(pivot: usize, string: &[char], words: &WordBoundary) -> usize {
     if pivot == 0 {
         pivot
     } else {
         let mut index = pivot - 1;
 
-        // At the end of the string, go through all trailing whitespace
+        // At the end of the string, go through all trailing separators
         if pivot == string.len() {
-            while index > 0 && string[index].is_whitespace() {
+            while index > 0 && words.is_separator(string[index]) {
                 index -= 1;
             }
         }
 
         // Go through the leading characters of the current word
-        while index > 0 && !string[index].is_whitespace() {
+        while index > 0 && !words.is_separator(string[index]) {
+            if words.splits(string[index - 1], string[index]) {
+                return index;
+            }
             index -= 1;
         }
 
-        // Go through the leading spaces of the current word
-        while index > 0 && string[index - 1].is_whitespace() {
+        // Go through the leading separators of the current word
+        while index > 0 && words.is_separator(string[index - 1]) {
             index -= 1;
         }
 
@@ -68,9 +82,71 @@ pub(super) fn previous_word_end(pivot: usize, string: &[char]) -> usize {
     }
 }
 
+pub(super) fn next_word_end(pivot: usize, string: &[char], words: &WordBoundary) -> usize {
+    let end = string.len();
+    let mut index = pivot + 1;
+
+    // Go through the leading separators, if any
+    while index < end && words.is_separator(string[index]) {
+        index += 1;
+    }
+
+    if index >= end {
+        end
+    } else {
+        // Go through the characters of the word, stopping at the last one
+        while index + 1 < end && joined(string, index + 1, words) {
+            index += 1;
+        }
+
+        index
+    }
+}
+
+pub(super) fn end_of_word(pivot: usize, string: &[char], words: &WordBoundary) -> usize {
+    let end = string.len();
+    let mut index = pivot.min(end);
+
+    // Go through the leading separators, if any
+    while index < end && words.is_separator(string[index]) {
+        index += 1;
+    }
+
+    // Go through the characters of the word, stopping right after the last one
+    if index < end {
+        index += 1;
+        while index < end && joined(string, index, words) {
+            index += 1;
+        }
+    }
+
+    index
+}
+
+pub(super) fn find_char(
+    pivot: usize,
+    string: &[char],
+    target: char,
+    direction: crate::actions::Direction,
+) -> Option<usize> {
+    use crate::actions::Direction::{Backward, Forward};
+
+    match direction {
+        Forward => string
+            .iter()
+            .skip(pivot + 1)
+            .position(|c| *c == target)
+            .map(|index| index + pivot + 1),
+        Backward => string[..pivot.min(string.len())]
+            .iter()
+            .rposition(|c| *c == target),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::super::CharString;
+    use crate::actions::WordBoundary;
 
     #[derive(Copy, Clone)]
     enum Direction {
@@ -117,10 +193,10 @@ mod test {
                     &uut,
                     &validator,
                     self.direction,
-                    &scenario,
-                    self.direction.start_for(&scenario),
+                    scenario,
+                    self.direction.start_for(scenario),
                     0,
-                )
+                );
             }
         }
     }
@@ -166,24 +242,104 @@ mod test {
     #[test]
     fn next_word() {
         let tester = Tester::prepare(Direction::Forward);
-        tester.test(super::next_word, |pivot, string| {
-            string[pivot] == 'A' || string[pivot] == 'O'
-        });
+        tester.test(
+            |pivot, string| super::next_word(pivot, string, &WordBoundary::Whitespace),
+            |pivot, string| string[pivot] == 'A' || string[pivot] == 'O',
+        );
     }
 
     #[test]
     fn previous_word() {
         let tester = Tester::prepare(Direction::Backward);
-        tester.test(super::previous_word, |pivot, string| {
-            string[pivot] == 'A' || string[pivot] == 'O'
-        });
+        tester.test(
+            |pivot, string| super::previous_word(pivot, string, &WordBoundary::Whitespace),
+            |pivot, string| string[pivot] == 'A' || string[pivot] == 'O',
+        );
+    }
+
+    #[test]
+    fn next_word_end() {
+        let tester = Tester::prepare(Direction::Forward);
+        tester.test(
+            |pivot, string| super::next_word_end(pivot, string, &WordBoundary::Whitespace),
+            |pivot, string| string[pivot] == 'Z' || string[pivot] == 'O',
+        );
+    }
+
+    #[test]
+    fn end_of_word() {
+        let tester = Tester::prepare(Direction::Forward);
+        tester.test(
+            |pivot, string| super::end_of_word(pivot, string, &WordBoundary::Whitespace),
+            |pivot, string| string[pivot - 1] == 'Z' || string[pivot - 1] == 'O',
+        );
+    }
+
+    #[test]
+    fn word_boundaries() {
+        let path = CharString::from("cd /usr/local_dir/bin");
+        let code = CharString::from("let snake_case = camelCase2Hump;");
+
+        let words = |string: &CharString, boundary: &WordBoundary| {
+            let mut pivots = Vec::new();
+            let mut pivot = 0;
+            while pivot < string.len() {
+                pivot = super::next_word(pivot, string, boundary);
+                pivots.push(pivot);
+            }
+            pivots
+        };
+
+        assert_eq!(words(&path, &WordBoundary::Whitespace), vec![3, 21]);
+        assert_eq!(words(&path, &WordBoundary::Punctuation), vec![4, 8, 18, 21]);
+        assert_eq!(words(&path, &WordBoundary::Subword), vec![4, 8, 14, 18, 21]);
+        assert_eq!(
+            words(&code, &WordBoundary::Subword),
+            vec![4, 10, 17, 22, 27, 32]
+        );
+        let separators = ['/', ' '];
+        assert_eq!(
+            words(
+                &path,
+                &WordBoundary::custom(move |c| separators.contains(&c))
+            ),
+            vec![4, 8, 18, 21]
+        );
+
+        // Punctuation is not limited to ASCII
+        let quote = CharString::from("«dit»—fait");
+        assert_eq!(words(&quote, &WordBoundary::Punctuation), vec![1, 6, 10]);
+        let accented = CharString::from("cafe\u{301} noir");
+        assert_eq!(words(&accented, &WordBoundary::Punctuation), vec![6, 10]);
+
+        let subword = &WordBoundary::Subword;
+        assert_eq!(super::previous_word(27, &code, subword), 22);
+        assert_eq!(super::previous_word(22, &code, subword), 17);
+        assert_eq!(super::previous_word_end(25, &code, subword), 22);
+        assert_eq!(super::next_word_end(17, &code, subword), 21);
+        assert_eq!(super::end_of_word(4, &code, subword), 9);
+    }
+
+    #[test]
+    fn find_char() {
+        use crate::actions::Direction::{Backward, Forward};
+
+        let string = CharString::from("a/b/c");
+        assert_eq!(super::find_char(0, &string, '/', Forward), Some(1));
+        assert_eq!(super::find_char(1, &string, '/', Forward), Some(3));
+        assert_eq!(super::find_char(3, &string, '/', Forward), None);
+        assert_eq!(super::find_char(4, &string, '/', Backward), Some(3));
+        assert_eq!(super::find_char(3, &string, '/', Backward), Some(1));
+        assert_eq!(super::find_char(1, &string, '/', Backward), None);
+        assert_eq!(super::find_char(0, &string, 'z', Forward), None);
     }
 
     #[test]
     fn previous_word_end() {
         let tester = Tester::prepare(Direction::Backward);
-        tester.test(super::previous_word_end, |pivot, string| {
-            string[pivot - 1] == 'Z' || string[pivot - 1] == 'O'
-        });
+        tester.test(
+            |pivot, string| super::previous_word_end(pivot, string, &WordBoundary::Whitespace),
+            |pivot, string| string[pivot - 1] == 'Z' || string[pivot - 1] == 'O',
+        );
     }
 }
@@ -0,0 +1,631 @@
+use crate::actions::{Direction, Event, WordBoundary};
+
+use super::{navigation, Buffer};
+
+/// The mode of the [`Vi`](struct.Vi.html) keymap.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(super) enum Mode {
+    /// Keys are written into the buffer
+    Insert,
+    /// Keys are interpreted as commands
+    Normal,
+}
+
+impl Mode {
+    /// The indicator printed before the prompt while in this mode.
+    pub(super) fn indicator(self) -> &'static str {
+        match self {
+            Mode::Insert => "(ins) ",
+            Mode::Normal => "(cmd) ",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Operator {
+    Delete,
+    Change,
+    Yank,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Search {
+    Find(Direction),
+    Till(Direction),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Motion {
+    Left,
+    Right,
+    WordForward,
+    WordBackward,
+    WordEnd,
+    CurrentWordEnd,
+    LineStart,
+    LineEnd,
+    WholeLine,
+    Search(Search, char),
+}
+
+/// Where a [`Motion`](enum.Motion.html) lands, and if an operator should include the landing
+/// character.
+struct Target {
+    position: usize,
+    inclusive: bool,
+}
+
+/// The outcome of feeding a key to a command.
+enum Step {
+    /// The command needs more keys
+    Pending,
+    /// The command finished without changing the buffer
+    Done,
+    /// The command finished and changed the buffer
+    Changed,
+    /// The command finished and switched to [`Insert`](enum.Mode.html#variant.Insert) mode
+    Inserting,
+}
+
+/// A change that can be repeated with `.`.
+struct Change {
+    keys: Vec<char>,
+    text: Vec<char>,
+}
+
+/// An ongoing insertion that will become a [`Change`](struct.Change.html) once it is over.
+struct Insertion {
+    keys: Vec<char>,
+    start: usize,
+}
+
+/// State machine for a vi-like keymap.
+///
+/// In [`Insert`] mode, all keys but `Esc` are left for the default actions. In [`Normal`] mode,
+/// keys are parsed into commands that are applied directly to the [`Buffer`].
+///
+/// [`Insert`]: enum.Mode.html#variant.Insert
+/// [`Normal`]: enum.Mode.html#variant.Normal
+/// [`Buffer`]: ../buffer/struct.Buffer.html
+pub(super) struct Vi {
+    mode: Mode,
+    count: Option<usize>,
+    operator: Option<(Operator, Option<usize>)>,
+    search: Option<Search>,
+    register: Vec<char>,
+    keys: Vec<char>,
+    insertion: Option<Insertion>,
+    last_change: Option<Change>,
+    words: WordBoundary,
+}
+
+impl Vi {
+    /// Creates a new state machine in [`Insert`](enum.Mode.html#variant.Insert) mode, whose
+    /// word motions follow `words`.
+    pub(super) fn new(words: WordBoundary) -> Self {
+        Self {
+            mode: Mode::Insert,
+            count: None,
+            operator: None,
+            search: None,
+            register: Vec::new(),
+            keys: Vec::new(),
+            insertion: None,
+            last_change: None,
+            words,
+        }
+    }
+
+    /// Returns the current mode.
+    #[inline]
+    pub(super) fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Returns `true` if `event` would be handled by this keymap instead of the default actions.
+    pub(super) fn claims(&self, event: Event) -> bool {
+        use crossterm::event::KeyCode::{Backspace, Char, Esc};
+
+        match self.mode {
+            Mode::Insert => event.code == Esc,
+            Mode::Normal => match event.code {
+                Esc | Backspace => true,
+                Char(_) => is_plain(event),
+                _ => false,
+            },
+        }
+    }
+
+    /// Handles a [`claimed`](struct.Vi.html#method.claims) event, updating `buffer` accordingly.
+    pub(super) fn handle(&mut self, event: Event, buffer: &mut Buffer) {
+        use crossterm::event::KeyCode::{Backspace, Char, Esc};
+
+        match (self.mode, event.code) {
+            (Mode::Insert, Esc) => self.leave_insert(buffer),
+            (Mode::Normal, Esc) => self.reset(),
+            (Mode::Normal, Backspace) => self.feed('h', buffer),
+            (Mode::Normal, Char(c)) => self.feed(c, buffer),
+            _ => {}
+        }
+
+        if self.mode == Mode::Normal && buffer.cursor() >= buffer.len() {
+            buffer.set_cursor(buffer.len().saturating_sub(1));
+        }
+    }
+
+    fn feed(&mut self, c: char, buffer: &mut Buffer) {
+        if c == '.' && self.is_idle() {
+            self.repeat(buffer);
+            return;
+        }
+
+        self.keys.push(c);
+        match self.command(c, buffer) {
+            Step::Pending => {}
+            Step::Done => self.keys.clear(),
+            Step::Changed => {
+                self.last_change = Some(Change {
+                    keys: std::mem::take(&mut self.keys),
+                    text: Vec::new(),
+                });
+            }
+            Step::Inserting => {
+                self.insertion = Some(Insertion {
+                    keys: std::mem::take(&mut self.keys),
+                    start: buffer.cursor(),
+                });
+            }
+        }
+    }
+
+    fn repeat(&mut self, buffer: &mut Buffer) {
+        let count = self.count.take().unwrap_or(1);
+        self.keys.clear();
+
+        if let Some(change) = self.last_change.take() {
+            for _ in 0..count {
+                for c in &change.keys {
+                    self.command(*c, buffer);
+                }
+
+                if self.mode == Mode::Insert {
+                    buffer.write_str(&change.text);
+                    self.leave_insert(buffer);
+                }
+            }
+            self.last_change = Some(change);
+        }
+    }
+
+    fn leave_insert(&mut self, buffer: &mut Buffer) {
+        self.mode = Mode::Normal;
+
+        if let Some(insertion) = self.insertion.take() {
+            let text = if buffer.cursor() > insertion.start {
+                buffer[insertion.start..buffer.cursor()].to_vec()
+            } else {
+                Vec::new()
+            };
+
+            self.last_change = Some(Change {
+                keys: insertion.keys,
+                text,
+            });
+        }
+
+        buffer.set_cursor(buffer.cursor().saturating_sub(1));
+    }
+
+    fn is_idle(&self) -> bool {
+        self.operator.is_none() && self.search.is_none()
+    }
+
+    fn reset(&mut self) {
+        self.count = None;
+        self.operator = None;
+        self.search = None;
+        self.keys.clear();
+    }
+
+    fn command(&mut self, c: char, buffer: &mut Buffer) -> Step {
+        use Direction::{Backward, Forward};
+
+        if let Some(search) = self.search.take() {
+            return self.apply(Motion::Search(search, c), buffer);
+        }
+
+        match c {
+            '0' if self.count.is_none() => self.apply(Motion::LineStart, buffer),
+            '0'..='9' => {
+                let digit = c.to_digit(10).map_or(0, |digit| digit as usize);
+                self.count = Some(self.count.unwrap_or(0) * 10 + digit);
+                Step::Pending
+            }
+            'f' => self.await_search(Search::Find(Forward)),
+            'F' => self.await_search(Search::Find(Backward)),
+            't' => self.await_search(Search::Till(Forward)),
+            'T' => self.await_search(Search::Till(Backward)),
+            'h' => self.apply(Motion::Left, buffer),
+            'l' | ' ' => self.apply(Motion::Right, buffer),
+            'w' => self.apply(Motion::WordForward, buffer),
+            'b' => self.apply(Motion::WordBackward, buffer),
+            'e' => self.apply(Motion::WordEnd, buffer),
+            '$' => self.apply(Motion::LineEnd, buffer),
+            'd' => self.operator(Operator::Delete, buffer),
+            'c' => self.operator(Operator::Change, buffer),
+            'y' => self.operator(Operator::Yank, buffer),
+            _ if self.operator.is_some() => {
+                self.reset();
+                Step::Done
+            }
+            'x' => self.apply_with(Operator::Delete, Motion::Right, buffer),
+            'X' => self.apply_with(Operator::Delete, Motion::Left, buffer),
+            'D' => self.apply_with(Operator::Delete, Motion::LineEnd, buffer),
+            'C' => self.apply_with(Operator::Change, Motion::LineEnd, buffer),
+            's' => self.apply_with(Operator::Change, Motion::Right, buffer),
+            'p' => self.paste(Forward, buffer),
+            'P' => self.paste(Backward, buffer),
+            'i' => self.insert(buffer.cursor(), buffer),
+            'a' => self.insert(buffer.cursor() + 1, buffer),
+            'I' => self.insert(0, buffer),
+            'A' => self.insert(buffer.len(), buffer),
+            _ => {
+                self.reset();
+                Step::Done
+            }
+        }
+    }
+
+    fn await_search(&mut self, search: Search) -> Step {
+        self.search = Some(search);
+        Step::Pending
+    }
+
+    fn operator(&mut self, operator: Operator, buffer: &mut Buffer) -> Step {
+        match self.operator {
+            // Doubling the operator applies it to the whole line, e.g. `dd`
+            Some((pending, _)) if pending == operator => self.apply(Motion::WholeLine, buffer),
+            Some(_) => {
+                self.reset();
+                Step::Done
+            }
+            None => {
+                self.operator = Some((operator, self.count.take()));
+                Step::Pending
+            }
+        }
+    }
+
+    fn apply_with(&mut self, operator: Operator, motion: Motion, buffer: &mut Buffer) -> Step {
+        self.operator = Some((operator, None));
+        self.apply(motion, buffer)
+    }
+
+    fn apply(&mut self, motion: Motion, buffer: &mut Buffer) -> Step {
+        let (operator, operator_count) = self
+            .operator
+            .take()
+            .map_or((None, None), |(o, c)| (Some(o), c));
+        let count = operator_count.unwrap_or(1) * self.count.take().unwrap_or(1);
+
+        // Like in vi, `cw` does not change the whitespace after the word
+        let motion = match (operator, motion) {
+            (Some(Operator::Change), Motion::WordForward)
+                if buffer
+                    .get(buffer.cursor())
+                    .is_some_and(|c| !self.words.is_separator(*c)) =>
+            {
+                Motion::CurrentWordEnd
+            }
+            _ => motion,
+        };
+
+        let Some(target) = target(motion, buffer, count, &self.words) else {
+            return Step::Done;
+        };
+
+        let cursor = buffer.cursor();
+        let (start, end) = if motion == Motion::WholeLine {
+            (0, buffer.len())
+        } else if target.position >= cursor {
+            let end = if target.inclusive {
+                target.position + 1
+            } else {
+                target.position
+            };
+            (cursor, end.min(buffer.len()))
+        } else {
+            (target.position, cursor)
+        };
+
+        match operator {
+            None => {
+                buffer.set_cursor(target.position);
+                Step::Done
+            }
+            Some(Operator::Yank) => {
+                self.register = buffer[start..end].to_vec();
+                buffer.set_cursor(start);
+                Step::Done
+            }
+            Some(Operator::Delete) => {
+                self.register = buffer.drain(start..end);
+                Step::Changed
+            }
+            Some(Operator::Change) => {
+                self.register = buffer.drain(start..end);
+                self.mode = Mode::Insert;
+                Step::Inserting
+            }
+        }
+    }
+
+    fn paste(&mut self, direction: Direction, buffer: &mut Buffer) -> Step {
+        let count = self.count.take().unwrap_or(1);
+        if self.register.is_empty() {
+            return Step::Done;
+        }
+
+        if direction == Direction::Forward && !buffer.is_empty() {
+            buffer.set_cursor(buffer.cursor() + 1);
+        }
+
+        for _ in 0..count {
+            buffer.write_str(&self.register);
+        }
+
+        // Rest on the last pasted character
+        buffer.set_cursor(buffer.cursor() - 1);
+        Step::Changed
+    }
+
+    fn insert(&mut self, cursor: usize, buffer: &mut Buffer) -> Step {
+        self.count = None;
+        self.mode = Mode::Insert;
+        buffer.set_cursor(cursor);
+        Step::Inserting
+    }
+}
+
+fn target(motion: Motion, buffer: &Buffer, count: usize, words: &WordBoundary) -> Option<Target> {
+    let cursor = buffer.cursor();
+    let exclusive = |position| {
+        Some(Target {
+            position,
+            inclusive: false,
+        })
+    };
+
+    match motion {
+        Motion::Left => exclusive(cursor.saturating_sub(count)),
+        Motion::Right => exclusive((cursor + count).min(buffer.len())),
+        Motion::WordForward => exclusive((0..count).fold(cursor, |pivot, _| {
+            navigation::next_word(pivot, buffer, words)
+        })),
+        Motion::WordBackward => exclusive((0..count).fold(cursor, |pivot, _| {
+            navigation::previous_word(pivot, buffer, words)
+        })),
+        Motion::WordEnd => Some(Target {
+            position: (0..count).fold(cursor, |pivot, _| {
+                navigation::next_word_end(pivot, buffer, words)
+            }),
+            inclusive: true,
+        }),
+        Motion::CurrentWordEnd => {
+            let mut position = cursor;
+            while position + 1 < buffer.len()
+                && !words.is_separator(buffer[position + 1])
+                && !words.splits(buffer[position], buffer[position + 1])
+            {
+                position += 1;
+            }
+
+            Some(Target {
+                position: (1..count).fold(position, |pivot, _| {
+                    navigation::next_word_end(pivot, buffer, words)
+                }),
+                inclusive: true,
+            })
+        }
+        Motion::LineStart => exclusive(0),
+        Motion::LineEnd | Motion::WholeLine => exclusive(buffer.len()),
+        Motion::Search(search, c) => {
+            let (direction, till) = match search {
+                Search::Find(direction) => (direction, false),
+                Search::Till(direction) => (direction, true),
+            };
+
+            let mut position = cursor;
+            for _ in 0..count {
+                position = navigation::find_char(position, buffer, c, direction)?;
+            }
+
+            match (direction, till) {
+                (Direction::Forward, false) => Some(Target {
+                    position,
+                    inclusive: true,
+                }),
+                (Direction::Forward, true) => Some(Target {
+                    position: position - 1,
+                    inclusive: true,
+                }),
+                (Direction::Backward, false) => exclusive(position),
+                (Direction::Backward, true) => exclusive(position + 1),
+            }
+        }
+    }
+}
+
+#[inline]
+fn is_plain(event: Event) -> bool {
+    use crossterm::event::KeyModifiers;
+    event.modifiers == KeyModifiers::NONE || event.modifiers == KeyModifiers::SHIFT
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Buffer, Mode, Vi};
+    use crate::actions::{Event, WordBoundary};
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn build_uut(string: &str) -> (Vi, Buffer) {
+        let chars = string.replace('_', "").chars().collect::<Vec<_>>();
+        let mut buffer = Buffer::from(chars.as_slice());
+        buffer.set_cursor(string.find('_').unwrap());
+
+        let mut vi = Vi::new(WordBoundary::Whitespace);
+        vi.mode = Mode::Normal;
+        (vi, buffer)
+    }
+
+    fn type_keys(vi: &mut Vi, buffer: &mut Buffer, keys: &str) {
+        for c in keys.chars() {
+            let event = match c {
+                '\x1b' => Event::from(KeyCode::Esc),
+                c if c.is_uppercase() => Event::new(KeyCode::Char(c), KeyModifiers::SHIFT),
+                c => Event::from(KeyCode::Char(c)),
+            };
+
+            if vi.claims(event) {
+                vi.handle(event, buffer);
+            } else if let KeyCode::Char(c) = event.code {
+                buffer.write(c);
+            }
+        }
+    }
+
+    fn assert_buffer(buffer: &Buffer, expected: &str) {
+        assert_eq!(buffer.to_string(), expected.replace('_', ""));
+        assert_eq!(buffer.cursor(), expected.find('_').unwrap());
+    }
+
+    #[test]
+    fn motions() {
+        let (mut vi, mut buffer) = build_uut("_one two/three four");
+
+        type_keys(&mut vi, &mut buffer, "w");
+        assert_buffer(&buffer, "one _two/three four");
+
+        type_keys(&mut vi, &mut buffer, "e");
+        assert_buffer(&buffer, "one two/thre_e four");
+
+        type_keys(&mut vi, &mut buffer, "b");
+        assert_buffer(&buffer, "one _two/three four");
+
+        type_keys(&mut vi, &mut buffer, "f/");
+        assert_buffer(&buffer, "one two_/three four");
+
+        type_keys(&mut vi, &mut buffer, "tr");
+        assert_buffer(&buffer, "one two/t_hree four");
+
+        type_keys(&mut vi, &mut buffer, "$");
+        assert_buffer(&buffer, "one two/three fou_r");
+
+        type_keys(&mut vi, &mut buffer, "0");
+        assert_buffer(&buffer, "_one two/three four");
+
+        type_keys(&mut vi, &mut buffer, "2w");
+        assert_buffer(&buffer, "one two/three _four");
+
+        type_keys(&mut vi, &mut buffer, "3h");
+        assert_buffer(&buffer, "one two/thr_ee four");
+    }
+
+    #[test]
+    fn operators() {
+        let (mut vi, mut buffer) = build_uut("_one two three four");
+
+        type_keys(&mut vi, &mut buffer, "dw");
+        assert_buffer(&buffer, "_two three four");
+
+        type_keys(&mut vi, &mut buffer, "2dw");
+        assert_buffer(&buffer, "_four");
+
+        type_keys(&mut vi, &mut buffer, "cwfive\x1b");
+        assert_buffer(&buffer, "fiv_e");
+        assert_eq!(vi.mode(), Mode::Normal);
+
+        type_keys(&mut vi, &mut buffer, "0ye$p");
+        assert_buffer(&buffer, "fivefiv_e");
+
+        type_keys(&mut vi, &mut buffer, "dd");
+        assert_buffer(&buffer, "_");
+
+        type_keys(&mut vi, &mut buffer, "P");
+        assert_buffer(&buffer, "fivefiv_e");
+    }
+
+    #[test]
+    fn operators_with_searches() {
+        let (mut vi, mut buffer) = build_uut("_/usr/local/bin");
+
+        type_keys(&mut vi, &mut buffer, "d2f/");
+        assert_buffer(&buffer, "_bin");
+
+        let (mut vi, mut buffer) = build_uut("/usr/local/bi_n");
+        type_keys(&mut vi, &mut buffer, "dT/");
+        assert_buffer(&buffer, "/usr/local/_n");
+
+        type_keys(&mut vi, &mut buffer, "0ct/var\x1b");
+        assert_buffer(&buffer, "va_r/local/n");
+    }
+
+    #[test]
+    fn delete_chars() {
+        let (mut vi, mut buffer) = build_uut("ab_cdef");
+
+        type_keys(&mut vi, &mut buffer, "x");
+        assert_buffer(&buffer, "ab_def");
+
+        type_keys(&mut vi, &mut buffer, "2x");
+        assert_buffer(&buffer, "ab_f");
+
+        type_keys(&mut vi, &mut buffer, "X");
+        assert_buffer(&buffer, "a_f");
+
+        type_keys(&mut vi, &mut buffer, "x");
+        assert_buffer(&buffer, "_a");
+    }
+
+    #[test]
+    fn repeat_last_change() {
+        let (mut vi, mut buffer) = build_uut("_a b c d e");
+
+        type_keys(&mut vi, &mut buffer, "dw");
+        assert_buffer(&buffer, "_b c d e");
+
+        type_keys(&mut vi, &mut buffer, ".");
+        assert_buffer(&buffer, "_c d e");
+
+        type_keys(&mut vi, &mut buffer, "wcwx\x1b");
+        assert_buffer(&buffer, "c _x e");
+
+        type_keys(&mut vi, &mut buffer, "w.");
+        assert_buffer(&buffer, "c x _x");
+
+        type_keys(&mut vi, &mut buffer, "0Ay\x1b");
+        assert_buffer(&buffer, "c x x_y");
+
+        type_keys(&mut vi, &mut buffer, "2.");
+        assert_buffer(&buffer, "c x xyy_y");
+    }
+
+    #[test]
+    fn switch_modes() {
+        let (mut vi, mut buffer) = build_uut("a_b");
+
+        type_keys(&mut vi, &mut buffer, "i");
+        assert_eq!(vi.mode(), Mode::Insert);
+        assert_buffer(&buffer, "a_b");
+
+        type_keys(&mut vi, &mut buffer, "x\x1b");
+        assert_eq!(vi.mode(), Mode::Normal);
+        assert_buffer(&buffer, "a_xb");
+
+        type_keys(&mut vi, &mut buffer, "az\x1b");
+        assert_buffer(&buffer, "ax_zb");
+
+        type_keys(&mut vi, &mut buffer, "Iw\x1bAy\x1b");
+        assert_buffer(&buffer, "waxzb_y");
+    }
+}
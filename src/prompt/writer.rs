@@ -1,51 +1,259 @@
-use super::{Buffer, CharString, CharStringView};
+use super::{Buffer, Candidate, CharString, CharStringView};
+
+/// Asks the terminal to surround pasted text with `ESC [200~` and `ESC [201~`.
+///
+/// The markers themselves are discarded by crossterm, but terminals in this mode also deliver
+/// the pasted text in a single burst, which is how the prompt guesses it apart from typing. The
+/// classic Windows console has no such mode, so nothing is sent to it.
+struct EnableBracketedPaste;
+
+impl crossterm::Command for EnableBracketedPaste {
+    type AnsiType = &'static str;
+
+    fn ansi_code(&self) -> Self::AnsiType {
+        "\x1b[?2004h"
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        Ok(())
+    }
+}
+
+/// Leaves the mode set by [`EnableBracketedPaste`](struct.EnableBracketedPaste.html).
+struct DisableBracketedPaste;
+
+impl crossterm::Command for DisableBracketedPaste {
+    type AnsiType = &'static str;
+
+    fn ansi_code(&self) -> Self::AnsiType {
+        "\x1b[?2004l"
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> crossterm::Result<()> {
+        Ok(())
+    }
+}
+
+/// The spaces between columns of suggestions, and between their labels and descriptions.
+const COLUMN_GAP: usize = 2;
+
+/// The most rows of suggestions shown at a time.
+const MAX_SUGGESTION_ROWS: usize = 10;
 
 // TODO: Keep track of lines (account for line breaks in CharString)
-pub(super) struct Writer {
+pub(super) struct Writer<'a> {
     erase_on_drop: Option<usize>,
     printed_length: usize,
     cursor_offset: usize,
+    prompt: Option<&'a CharString>,
+    prompt_width: usize,
+    right_prompt: Option<RightPrompt<'a>>,
+    completion_length: usize,
+    grid: Option<Grid>,
+    capturing_mouse: bool,
+    bracketing_pastes: bool,
 }
 
-impl Writer {
+/// What the writer drew at a position of the terminal.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(super) enum Drawn {
+    /// The buffer, at the given index
+    Buffer(usize),
+    /// The suggestion with the given index
+    Suggestion(usize),
+}
+
+/// How the suggestions are laid out below the buffer: in a grid, filled row by row, of which
+/// only a page of rows is shown at a time.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(super) struct Grid {
+    /// The number of suggestions on each row
+    pub(super) columns: usize,
+    /// The number of rows on each page
+    pub(super) page_rows: usize,
+    /// The width of each column, including the gap after it
+    column_width: usize,
+    /// The width of the terminal, which labels and descriptions are cut short at
+    width: usize,
+    /// The index of the first suggestion shown
+    first: usize,
+    /// The number of suggestions shown
+    shown: usize,
+}
+
+/// A prompt rendered flush right on the line of the buffer.
+struct RightPrompt<'a> {
+    text: &'a CharString,
+    width: usize,
+    buffer_column: usize,
+}
+
+impl<'a> Writer<'a> {
     pub(super) fn new(
         erase_on_drop: bool,
-        prompt: Option<&CharString>,
+        prompt: Option<&'a CharString>,
+        right_prompt: Option<&'a CharString>,
+        indicator: Option<&str>,
     ) -> Result<Self, crate::ErrorKind> {
-        crossterm::terminal::enable_raw_mode()?;
-        if let Some(prompt) = prompt {
-            use std::io::Write;
-
-            crossterm::queue!(std::io::stdout(), crossterm::style::Print(prompt))?;
-        }
-
+        let indicator_length = indicator.map_or(0, str::len);
         let erase_on_drop = if erase_on_drop {
-            prompt.map(CharString::len).or(Some(0))
+            prompt
+                .map(CharString::len)
+                .or(Some(0))
+                .map(|length| length + indicator_length)
         } else {
             None
         };
 
-        Ok(Self {
+        let mut writer = Self {
             erase_on_drop,
             printed_length: 0,
             cursor_offset: 0,
-        })
+            prompt,
+            prompt_width: indicator_length + prompt.map_or(0, CharString::printable_len),
+            right_prompt: right_prompt.map(|text| RightPrompt {
+                text,
+                width: text.printable_len(),
+                buffer_column: 0,
+            }),
+            completion_length: 0,
+            grid: None,
+            capturing_mouse: false,
+            bracketing_pastes: false,
+        };
+
+        writer.start(indicator)?;
+        Ok(writer)
+    }
+
+    /// Takes over the terminal and prints the prompt, preceded by the `indicator`.
+    fn start(&mut self, indicator: Option<&str>) -> Result<(), crate::ErrorKind> {
+        use std::io::Write;
+        let mut stdout = std::io::stdout();
+
+        crossterm::terminal::enable_raw_mode()?;
+        if self.bracketing_pastes {
+            crossterm::queue!(stdout, EnableBracketedPaste)?;
+        }
+        if self.capturing_mouse {
+            crossterm::queue!(stdout, crossterm::event::EnableMouseCapture)?;
+        }
+        if let Some(indicator) = indicator {
+            crossterm::queue!(stdout, crossterm::style::Print(indicator))?;
+        }
+        if let Some(prompt) = self.prompt {
+            crossterm::queue!(stdout, crossterm::style::Print(prompt))?;
+        }
+
+        // Only query the cursor when needed, since it requires a round trip to the terminal
+        if let Some(right_prompt) = &mut self.right_prompt {
+            right_prompt.buffer_column =
+                crossterm::cursor::position().map(|pos| usize::from(pos.0))?;
+        }
+
+        Ok(())
+    }
+
+    /// Hands the terminal back, leaving what was printed on a line of its own, until
+    /// [`resume`](#method.resume) is called.
+    pub(super) fn suspend(&mut self) -> Result<(), crate::ErrorKind> {
+        use std::io::Write;
+        let mut stdout = std::io::stdout();
+
+        fast_forward_cursor(&mut stdout, self.cursor_offset)?;
+        crossterm::queue!(
+            stdout,
+            crossterm::terminal::Clear(crossterm::terminal::ClearType::FromCursorDown)
+        )?;
+        if self.bracketing_pastes {
+            crossterm::queue!(stdout, DisableBracketedPaste)?;
+        }
+        if self.capturing_mouse {
+            crossterm::queue!(stdout, crossterm::event::DisableMouseCapture)?;
+        }
+        crossterm::terminal::disable_raw_mode()?;
+        crossterm::execute!(stdout, crossterm::style::Print('\n'))?;
+
+        self.printed_length = 0;
+        self.cursor_offset = 0;
+        self.completion_length = 0;
+        self.grid = None;
+        Ok(())
+    }
+
+    /// Takes the terminal back after a [`suspend`](#method.suspend) and prints the prompt again,
+    /// preceded by the `indicator`.
+    pub(super) fn resume(&mut self, indicator: Option<&str>) -> Result<(), crate::ErrorKind> {
+        self.start(indicator)
+    }
+
+    /// Reports mouse events until the writer is dropped.
+    pub(super) fn capture_mouse(&mut self) -> Result<(), crate::ErrorKind> {
+        use std::io::Write;
+
+        self.capturing_mouse = true;
+        crossterm::execute!(std::io::stdout(), crossterm::event::EnableMouseCapture)
+    }
+
+    /// Asks the terminal to deliver pastes in a single burst until the writer is dropped.
+    pub(super) fn bracket_pastes(&mut self) -> Result<(), crate::ErrorKind> {
+        use std::io::Write;
+
+        self.bracketing_pastes = true;
+        crossterm::execute!(std::io::stdout(), EnableBracketedPaste)
+    }
+
+    /// The layout of the suggestions printed below the buffer, if any.
+    pub(super) fn grid(&self) -> Option<Grid> {
+        self.grid
+    }
+
+    /// Finds what was drawn at the `column` and `row` of the terminal, if anything.
+    pub(super) fn drawn_at(
+        &self,
+        column: u16,
+        row: u16,
+    ) -> Result<Option<Drawn>, crate::ErrorKind> {
+        let index = self.printed_length - self.cursor_offset;
+        let drawn = locate(
+            crossterm::cursor::position()?,
+            index,
+            self.printed_length,
+            self.grid,
+            (column, row),
+        );
+
+        // The completion drawn after the cursor is not part of the buffer
+        Ok(drawn.map(|drawn| match drawn {
+            Drawn::Buffer(clicked) if clicked > index => {
+                Drawn::Buffer(clicked.saturating_sub(self.completion_length).max(index))
+            }
+            drawn => drawn,
+        }))
     }
 
     pub(super) fn print(
         &mut self,
         buffer: &Buffer,
-        completion: Option<CharStringView<'_>>,
+        completion: Option<&[char]>,
     ) -> Result<(), crate::ErrorKind> {
         use std::io::Write;
         let mut stdout = std::io::stdout();
+        let completion = completion.map(CharStringView::from);
 
         clear_from(&mut stdout, self.printed_length - self.cursor_offset)?;
 
         self.cursor_offset = buffer.len() - buffer.cursor();
         self.printed_length = buffer.len();
+        self.completion_length = 0;
+        self.grid = None;
 
-        crossterm::queue!(&mut stdout, crossterm::style::Print(&buffer))?;
+        // The completion is inserted at the cursor, which is usually at the end of the buffer
+        let cursor = buffer.cursor();
+        let selection = buffer.selection().unwrap_or(cursor..cursor);
+        print_styled(&mut stdout, buffer, 0..cursor, &selection)?;
 
         if let Some(completion) = completion {
             use crossterm::style::Colorize;
@@ -53,61 +261,115 @@ impl Writer {
                 &mut stdout,
                 crossterm::style::PrintStyledContent(crossterm::style::style(completion).blue())
             )?;
-            rewind_cursor(&mut stdout, completion.len())?;
+            self.cursor_offset += completion.len();
+            self.printed_length += completion.len();
+            self.completion_length = completion.len();
         }
 
+        print_styled(&mut stdout, buffer, cursor..buffer.len(), &selection)?;
+        self.print_right_prompt(&mut stdout, self.printed_length)?;
+
         rewind_cursor(&mut stdout, self.cursor_offset)?;
         crossterm::execute!(&mut stdout)
     }
 
     pub(super) fn print_suggestions(
         &mut self,
+        buffer: &[char],
         selected_index: usize,
-        suggestions: &[CharStringView<'_>],
+        candidates: &[Candidate<'_>],
     ) -> Result<(), crate::ErrorKind> {
         use std::io::Write;
         let mut stdout = std::io::stdout();
 
-        // Print buffer
-        let buffer = suggestions[selected_index];
+        // Print buffer with the selected candidate in place
+        let (preview, cursor) = candidates[selected_index].apply(buffer);
+        let preview = CharStringView::from(&preview[..]);
         clear_from(&mut stdout, self.printed_length - self.cursor_offset)?;
-        crossterm::queue!(stdout, crossterm::style::Print(buffer))?;
-        self.cursor_offset = 0;
-        self.printed_length = buffer.len();
+        crossterm::queue!(stdout, crossterm::style::Print(preview))?;
+        self.cursor_offset = preview.len() - cursor;
+        self.printed_length = preview.len();
+        self.completion_length = 0;
+        self.print_right_prompt(&mut stdout, preview.len())?;
 
         // Save position at the end of the buffer
         // TODO: avoid this save and the later restore
         let end_of_buffer = crossterm::cursor::position().map(|pos| pos.0)?;
 
+        let terminal = crossterm::terminal::size()?;
+        let grid = Grid::new(
+            candidates,
+            selected_index,
+            (usize::from(terminal.0), usize::from(terminal.1)),
+        );
+        self.grid = Some(grid);
+
         // Print suggestions
-        for (index, suggestion) in suggestions.iter().enumerate() {
-            if index == selected_index {
+        let page = &candidates[grid.first..grid.first + grid.shown];
+        let mut rows: u16 = 0;
+        for (row, chunk) in page.chunks(grid.columns).enumerate() {
+            crossterm::queue!(
+                stdout,
+                crossterm::style::Print('\n'),
+                crossterm::cursor::MoveToColumn(0),
+            )?;
+            rows += 1;
+
+            for (column, candidate) in chunk.iter().enumerate() {
                 use crossterm::style::Styler;
-                crossterm::queue!(
-                    stdout,
-                    crossterm::style::Print('\n'),
-                    crossterm::cursor::MoveToColumn(0),
-                    crossterm::style::PrintStyledContent(
-                        crossterm::style::style(suggestion).bold()
-                    ),
-                )?;
-            } else {
-                crossterm::queue!(
-                    stdout,
-                    crossterm::style::Print('\n'),
-                    crossterm::cursor::MoveToColumn(0),
-                    crossterm::style::Print(suggestion),
-                )?;
+
+                // Labels are cut short at the width of the terminal so that no row wraps
+                let shown = candidate.label().len().min(grid.width);
+
+                // Highlighted characters are underlined, the selected label is bold
+                let selected = grid.first + row * grid.columns + column == selected_index;
+                for (run, highlighted) in label_runs(shown, candidate.highlights()) {
+                    let mut label =
+                        crossterm::style::style(CharStringView::from(&candidate.label()[run]));
+                    if selected {
+                        label = label.bold();
+                    }
+                    if highlighted {
+                        label = label.underlined();
+                    }
+                    crossterm::queue!(stdout, crossterm::style::PrintStyledContent(label))?;
+                }
+
+                // Descriptions are aligned after the longest label
+                let padding = grid.column_width - shown;
+                let room = grid.width.saturating_sub(grid.column_width);
+                if let Some(description) = candidate.description().filter(|_| room > 0) {
+                    let description = &description[..description.len().min(room)];
+                    crossterm::queue!(
+                        stdout,
+                        crossterm::style::Print(" ".repeat(padding)),
+                        crossterm::style::PrintStyledContent(
+                            crossterm::style::style(CharStringView::from(description)).dim()
+                        ),
+                    )?;
+                } else if column + 1 < chunk.len() {
+                    crossterm::queue!(stdout, crossterm::style::Print(" ".repeat(padding)))?;
+                }
             }
         }
 
-        // Rewind suggestions cursor
-        for suggestion in suggestions.iter().rev() {
-            let length = suggestion.len();
-            rewind_cursor(&mut stdout, length)?;
-            crossterm::queue!(stdout, crossterm::cursor::MoveUp(1))?;
+        let hidden = candidates.len() - grid.shown;
+        if hidden > 0 {
+            use crossterm::style::Styler;
+            crossterm::queue!(
+                stdout,
+                crossterm::style::Print('\n'),
+                crossterm::cursor::MoveToColumn(0),
+                crossterm::style::PrintStyledContent(
+                    crossterm::style::style(format!("{hidden} more...")).dim()
+                ),
+            )?;
+            rows += 1;
         }
 
+        // Rewind suggestions cursor
+        crossterm::queue!(stdout, crossterm::cursor::MoveUp(rows))?;
+
         // Restore cursor
         let bottom_of_buffer = crossterm::cursor::position().map(|pos| pos.1)?;
         crossterm::queue!(
@@ -119,6 +381,153 @@ impl Writer {
         // Execute
         crossterm::execute!(stdout)
     }
+
+    /// Replaces the mode indicator printed before the prompt, without moving the cursor.
+    ///
+    /// The new `indicator` must have the same length as the one given when building the writer.
+    pub(super) fn print_indicator(&mut self, indicator: &str) -> Result<(), crate::ErrorKind> {
+        use std::io::Write;
+        let mut stdout = std::io::stdout();
+
+        crossterm::queue!(stdout, crossterm::cursor::SavePosition)?;
+        rewind_cursor(
+            &mut stdout,
+            self.printed_length - self.cursor_offset + self.prompt_width,
+        )?;
+        crossterm::execute!(
+            stdout,
+            crossterm::style::Print(indicator),
+            crossterm::cursor::RestorePosition
+        )
+    }
+
+    /// Prints the right prompt, if any, without moving the cursor.
+    ///
+    /// The right prompt is omitted when the `printed_length` after the start of the buffer would
+    /// collide with it.
+    fn print_right_prompt(
+        &self,
+        stdout: &mut std::io::Stdout,
+        printed_length: usize,
+    ) -> Result<(), crate::ErrorKind> {
+        use std::convert::TryFrom;
+        use std::io::Write;
+
+        if let Some(right_prompt) = &self.right_prompt {
+            let columns = usize::from(crossterm::terminal::size()?.0);
+
+            // Keep at least one column between the buffer and the right prompt
+            if right_prompt.buffer_column + printed_length + right_prompt.width < columns {
+                // `MoveTo` is 0-based on every backend, unlike `MoveToColumn`
+                let column = u16::try_from(columns - right_prompt.width).unwrap_or(u16::MAX);
+                let row = crossterm::cursor::position()?.1;
+                crossterm::queue!(
+                    stdout,
+                    crossterm::cursor::SavePosition,
+                    crossterm::cursor::MoveTo(column, row),
+                    crossterm::style::Print(right_prompt.text),
+                    crossterm::cursor::RestorePosition,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Splits a label of `length` characters into runs that are either all highlighted or not.
+fn label_runs(length: usize, highlights: &[usize]) -> Vec<(std::ops::Range<usize>, bool)> {
+    let mut runs: Vec<(std::ops::Range<usize>, bool)> = Vec::new();
+    for index in 0..length {
+        let highlighted = highlights.contains(&index);
+        match runs.last_mut() {
+            Some((run, last)) if *last == highlighted => run.end = index + 1,
+            _ => runs.push((index..index + 1, highlighted)),
+        }
+    }
+    runs
+}
+
+/// Prints the `range` of the `buffer`, in reverse video where it overlaps the `selection`.
+fn print_styled(
+    stdout: &mut std::io::Stdout,
+    buffer: &Buffer,
+    range: std::ops::Range<usize>,
+    selection: &std::ops::Range<usize>,
+) -> Result<(), crate::ErrorKind> {
+    use crossterm::style::Styler;
+    use std::io::Write;
+
+    let start = selection.start.clamp(range.start, range.end);
+    let end = selection.end.clamp(range.start, range.end);
+    let text = |range: std::ops::Range<usize>| buffer[range].iter().collect::<String>();
+
+    crossterm::queue!(
+        stdout,
+        crossterm::style::Print(text(range.start..start)),
+        crossterm::style::PrintStyledContent(crossterm::style::style(text(start..end)).reverse()),
+        crossterm::style::Print(text(end..range.end)),
+    )
+}
+
+impl Grid {
+    /// Lays out the `candidates` for a terminal of the given `size`, in columns and rows, on
+    /// the page that shows the `selected` one.
+    ///
+    /// Candidates with descriptions are laid out in a single column, so the descriptions line
+    /// up. Labels wider than the terminal are cut short at its width. One row of the terminal is
+    /// kept for the buffer and another for the count of hidden candidates.
+    fn new(candidates: &[Candidate<'_>], selected: usize, size: (usize, usize)) -> Self {
+        let count = candidates.len();
+        let widest = candidates
+            .iter()
+            .map(|candidate| candidate.label().len())
+            .max()
+            .unwrap_or(0);
+        let column_width = widest.min(size.0) + COLUMN_GAP;
+
+        let columns = if candidates
+            .iter()
+            .any(|candidate| candidate.description().is_some())
+        {
+            1
+        } else {
+            ((size.0 + COLUMN_GAP) / column_width).clamp(1, count.max(1))
+        };
+        let page_rows = size
+            .1
+            .saturating_sub(2)
+            .clamp(1, MAX_SUGGESTION_ROWS)
+            .min(count.div_ceil(columns).max(1));
+
+        let page_size = columns * page_rows;
+        let first = selected.min(count.saturating_sub(1)) / page_size * page_size;
+
+        Self {
+            columns,
+            page_rows,
+            column_width,
+            width: size.0,
+            first,
+            shown: (count - first).min(page_size),
+        }
+    }
+
+    /// The index of the candidate at the `row` and `column` of the terminal, counted from the
+    /// first row below the buffer.
+    fn index_at(&self, row: usize, column: usize) -> Option<usize> {
+        let cell = column / self.column_width;
+        if cell >= self.columns {
+            return None;
+        }
+
+        let offset = row * self.columns + cell;
+        if offset < self.shown {
+            Some(self.first + offset)
+        } else {
+            None
+        }
+    }
 }
 
 fn clear_from(stdout: &mut std::io::Stdout, amount: usize) -> Result<(), crate::ErrorKind> {
@@ -132,6 +541,28 @@ fn clear_from(stdout: &mut std::io::Stdout, amount: usize) -> Result<(), crate::
     )
 }
 
+/// Finds what was drawn at `click`, given the position of the `cursor`, its index in the
+/// buffer, the length of the buffer and the grid of suggestions printed below it, if any.
+fn locate(
+    cursor: (u16, u16),
+    cursor_index: usize,
+    printed_length: usize,
+    grid: Option<Grid>,
+    click: (u16, u16),
+) -> Option<Drawn> {
+    let (column, row) = (usize::from(click.0), usize::from(click.1));
+    let buffer_row = usize::from(cursor.1);
+
+    if row == buffer_row {
+        let buffer_column = usize::from(cursor.0).checked_sub(cursor_index)?;
+        let index = column.checked_sub(buffer_column)?;
+        Some(Drawn::Buffer(index.min(printed_length)))
+    } else {
+        let row = row.checked_sub(buffer_row + 1)?;
+        grid?.index_at(row, column).map(Drawn::Suggestion)
+    }
+}
+
 // Allowed because we slice `usize` into `u16` chunks
 #[allow(clippy::cast_possible_truncation)]
 fn rewind_cursor(stdout: &mut std::io::Stdout, amount: usize) -> Result<(), crate::ErrorKind> {
@@ -142,9 +573,9 @@ fn rewind_cursor(stdout: &mut std::io::Stdout, amount: usize) -> Result<(), crat
     }
 
     let mut remaining = amount;
-    while remaining > usize::from(u16::max_value()) {
-        crossterm::queue!(stdout, crossterm::cursor::MoveLeft(u16::max_value()))?;
-        remaining -= usize::from(u16::max_value());
+    while remaining > usize::from(u16::MAX) {
+        crossterm::queue!(stdout, crossterm::cursor::MoveLeft(u16::MAX))?;
+        remaining -= usize::from(u16::MAX);
     }
 
     crossterm::queue!(stdout, crossterm::cursor::MoveLeft(remaining as u16))
@@ -163,15 +594,15 @@ fn fast_forward_cursor(
     }
 
     let mut remaining = amount;
-    while remaining > usize::from(u16::max_value()) {
-        crossterm::queue!(stdout, crossterm::cursor::MoveRight(u16::max_value()))?;
-        remaining -= usize::from(u16::max_value());
+    while remaining > usize::from(u16::MAX) {
+        crossterm::queue!(stdout, crossterm::cursor::MoveRight(u16::MAX))?;
+        remaining -= usize::from(u16::MAX);
     }
 
     crossterm::queue!(stdout, crossterm::cursor::MoveRight(remaining as u16))
 }
 
-impl std::ops::Drop for Writer {
+impl std::ops::Drop for Writer<'_> {
     // Allowed because this is a drop and the previous construction already managed the get through
     #[allow(unused_must_use)]
     fn drop(&mut self) {
@@ -179,10 +610,17 @@ impl std::ops::Drop for Writer {
         crossterm::terminal::disable_raw_mode();
 
         let mut stdout = std::io::stdout();
+        if self.bracketing_pastes {
+            crossterm::queue!(stdout, DisableBracketedPaste);
+        }
+        if self.capturing_mouse {
+            crossterm::queue!(stdout, crossterm::event::DisableMouseCapture);
+        }
 
         if let Some(prompt_length) = self.erase_on_drop {
             clear_from(&mut stdout, self.printed_length + prompt_length);
         } else {
+            // Clearing from the end of the buffer also removes the right prompt
             fast_forward_cursor(&mut stdout, self.cursor_offset);
             crossterm::execute!(
                 stdout,
@@ -192,3 +630,92 @@ impl std::ops::Drop for Writer {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{locate, Drawn, Grid};
+    use crate::completion::Candidate;
+
+    fn candidates(labels: &[&str]) -> Vec<Candidate<'static>> {
+        labels
+            .iter()
+            .map(|label| Candidate::new(label.chars().collect::<Vec<_>>(), 0..0))
+            .collect()
+    }
+
+    #[test]
+    fn locate_clicks() {
+        // The buffer "abcd" starts at column 2 of row 5, with the cursor after "ab"
+        let grid = Grid::new(&candidates(&["one", "two", "six"]), 0, (10, 20));
+        let locate = |column, row| locate((4, 5), 2, 4, Some(grid), (column, row));
+
+        assert_eq!(locate(2, 5), Some(Drawn::Buffer(0)));
+        assert_eq!(locate(5, 5), Some(Drawn::Buffer(3)));
+        assert_eq!(locate(20, 5), Some(Drawn::Buffer(4)));
+        assert_eq!(locate(0, 5), None);
+        assert_eq!(locate(0, 6), Some(Drawn::Suggestion(0)));
+        assert_eq!(locate(6, 6), Some(Drawn::Suggestion(1)));
+        assert_eq!(locate(0, 7), Some(Drawn::Suggestion(2)));
+        assert_eq!(locate(6, 7), None);
+        assert_eq!(locate(0, 8), None);
+        assert_eq!(locate(3, 4), None);
+        assert_eq!(super::locate((4, 5), 2, 4, None, (0, 6)), None);
+    }
+
+    #[test]
+    fn split_highlighted_runs() {
+        assert_eq!(
+            super::label_runs(6, &[0, 1, 4]),
+            vec![(0..2, true), (2..4, false), (4..5, true), (5..6, false)]
+        );
+        assert_eq!(super::label_runs(3, &[]), vec![(0..3, false)]);
+        assert!(super::label_runs(0, &[1]).is_empty());
+    }
+
+    #[test]
+    fn lay_out_in_columns() {
+        let labels = candidates(&["a", "bb", "ccc", "d", "e"]);
+
+        // Columns of "ccc" and a gap fit three times in 15 characters
+        let grid = Grid::new(&labels, 0, (15, 20));
+        assert_eq!((grid.columns, grid.page_rows, grid.shown), (3, 2, 5));
+
+        // Never more columns than candidates, and never less than one
+        assert_eq!(Grid::new(&labels, 0, (100, 20)).columns, 5);
+        assert_eq!(Grid::new(&labels, 0, (2, 20)).columns, 1);
+
+        // Descriptions get a column of their own
+        let mut described = labels.clone();
+        described[0] = described[0].clone().with_description(vec!['x']);
+        assert_eq!(Grid::new(&described, 0, (100, 20)).columns, 1);
+
+        // Labels wider than the terminal are cut short at its width
+        let wide = candidates(&["a", &"w".repeat(30)]);
+        let grid = Grid::new(&wide, 0, (10, 20));
+        assert_eq!((grid.columns, grid.column_width), (1, 12));
+    }
+
+    #[test]
+    fn page_through_rows() {
+        let labels = candidates(&["a", "b", "c", "d", "e", "f", "g"]);
+
+        // Two rows fit below the buffer in a terminal of four rows
+        let grid = Grid::new(&labels, 0, (1, 4));
+        assert_eq!((grid.page_rows, grid.first, grid.shown), (2, 0, 2));
+
+        let grid = Grid::new(&labels, 3, (1, 4));
+        assert_eq!((grid.first, grid.shown), (2, 2));
+
+        let grid = Grid::new(&labels, 6, (1, 4));
+        assert_eq!((grid.first, grid.shown), (6, 1));
+        assert_eq!(grid.index_at(0, 0), Some(6));
+        assert_eq!(grid.index_at(1, 0), None);
+
+        // The menu is never taller than the cap
+        let many = candidates(&["x"; 50]);
+        assert_eq!(
+            Grid::new(&many, 0, (1, 100)).page_rows,
+            super::MAX_SUGGESTION_ROWS
+        );
+    }
+}